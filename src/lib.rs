@@ -51,6 +51,7 @@ mod test_util {
                 package_todo: Default::default(),
                 visible_to: Default::default(),
                 public_folder: Default::default(),
+                public_paths: Default::default(),
                 layer: Default::default(),
                 enforce_dependencies: Default::default(),
                 enforce_privacy: Default::default(),
@@ -61,6 +62,11 @@ mod test_util {
                 client_keys: Default::default(),
                 owner: Default::default(),
                 enforcement_globs_ignore: Default::default(),
+                violation_budget: Default::default(),
+                todo_deadline: Default::default(),
+                record_incoming_violations: Default::default(),
+                gem_dependencies: Default::default(),
+                enforce_gem_dependencies: Default::default(),
             }
         }
     }
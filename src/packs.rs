@@ -4,10 +4,12 @@ pub mod cli;
 
 // Module declarations
 pub(crate) mod bin_locater;
+pub(crate) mod blame;
 pub(crate) mod caching;
 pub(crate) mod checker;
 pub(crate) mod configuration;
 pub(crate) mod constant_resolver;
+pub(crate) mod date;
 pub(crate) mod dependencies;
 pub(crate) mod ignored;
 pub(crate) mod monkey_patch_detection;
@@ -24,6 +26,7 @@ mod package_todo;
 mod reference_extractor;
 
 use crate::packs;
+use crate::packs::constant_resolver::ConstantResolver;
 use crate::packs::pack::write_pack_to_disk;
 use crate::packs::pack::Pack;
 
@@ -37,6 +40,8 @@ pub(crate) use self::parsing::ParsedDefinition;
 pub(crate) use self::parsing::UnresolvedReference;
 use anyhow::bail;
 pub(crate) use configuration::Configuration;
+use configuration::OutputFormat;
+use configuration::TodoStorage;
 pub(crate) use package_todo::PackageTodo;
 
 // External imports
@@ -170,15 +175,48 @@ pub fn check(
 ) -> anyhow::Result<()> {
     let result = checker::check_all(configuration, files)
         .context("Failed to check files")?;
-    println!("{}", result);
+    match configuration.output_format {
+        OutputFormat::Text => println!("{}", result),
+        OutputFormat::Markdown => println!("{}", result.to_markdown()),
+        OutputFormat::Json => println!("{}", result.to_json()),
+    }
     if result.has_violations() {
         bail!("Violations found!")
     }
     Ok(())
 }
 
-pub fn update(configuration: &Configuration) -> anyhow::Result<()> {
-    checker::update(configuration)
+pub fn update(
+    configuration: &Configuration,
+    pack_names: Vec<String>,
+    files: Vec<String>,
+) -> anyhow::Result<()> {
+    checker::update(configuration, pack_names, files)
+}
+
+pub fn promote_strict(configuration: &Configuration) -> anyhow::Result<()> {
+    checker::promote_strict(configuration)
+}
+
+pub fn merge_todo(
+    configuration: &Configuration,
+    ancestor: &Path,
+    current: &Path,
+    other: &Path,
+) -> anyhow::Result<()> {
+    package_todo::merge_package_todo_files(
+        configuration,
+        ancestor,
+        current,
+        other,
+    )
+}
+
+pub fn convert_todo_storage(
+    configuration: &Configuration,
+    target: TodoStorage,
+) -> anyhow::Result<()> {
+    package_todo::convert_todo_storage(configuration, target)
 }
 
 pub fn add_dependency(
@@ -330,48 +368,122 @@ pub struct ProcessedFile {
 
     #[serde(default)] // Default to an empty Vec if not present
     pub sigils: Vec<Sigil>,
+
+    #[serde(default)]
+    pub suppressions: Vec<Suppression>,
 }
 
 // A sigil is a way to specify some packs specific behavior at the top of a file, like
-// `# pack_public: true`. This struct picks up sigil names, which are an enum of string values, starting with just one possibility.
-// and value, which is a boolean
+// `# pack_public: true` or `# pack_visible_to: [packs/a]`. The name is what follows `pack_`.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct Sigil {
     pub name: String,
-    pub value: bool,
+    pub value: SigilValue,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SigilValue {
+    Bool(bool),
+    List(Vec<String>),
+    String(String),
+}
+
+impl SigilValue {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            SigilValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    // A single value is treated as a list of one
+    pub fn as_list(&self) -> Option<Vec<String>> {
+        match self {
+            SigilValue::List(values) => Some(values.clone()),
+            SigilValue::String(value) => Some(vec![value.clone()]),
+            SigilValue::Bool(_) => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SigilValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, Default, Eq, Clone)]
+// An inline comment that suppresses violations of the given types at one location, e.g.
+// `# packs:disable-next-line privacy` or `Foo # packs:disable dependency -- reason`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    // The 1-based line of the comment
+    pub line: usize,
+    pub next_line: bool,
+    pub violation_types: Vec<String>,
+    pub reason: Option<String>,
+}
+
+impl Suppression {
+    // The line whose violations are suppressed
+    pub fn target_line(&self) -> usize {
+        if self.next_line {
+            self.line + 1
+        } else {
+            self.line
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Default,
+    Eq,
+    Hash,
+    Clone,
+    PartialOrd,
+    Ord,
+)]
 pub struct SourceLocation {
     line: usize,
     column: usize,
 }
 
-pub(crate) fn list_definitions(
+pub(crate) fn get_constant_resolver(
     configuration: &Configuration,
-    ambiguous: bool,
-) -> anyhow::Result<()> {
-    let constant_resolver = if configuration.experimental_parser {
+) -> anyhow::Result<Box<dyn ConstantResolver + Send + Sync>> {
+    if configuration.experimental_parser {
         let processed_files: Vec<ProcessedFile> = process_files_with_cache(
             &configuration.included_files,
             configuration.get_cache(),
             configuration,
         )?;
 
-        get_experimental_constant_resolver(
+        Ok(get_experimental_constant_resolver(
             &configuration.absolute_root,
             &processed_files,
             &configuration.ignored_definitions,
-        )
+        ))
     } else {
-        if ambiguous {
-            bail!("Ambiguous mode is not supported for the Zeitwerk parser");
-        }
-        get_zeitwerk_constant_resolver(
+        Ok(get_zeitwerk_constant_resolver(
             &configuration.pack_set,
             &configuration.constant_resolver_configuration(),
-        )
-    };
+        ))
+    }
+}
+
+pub(crate) fn list_definitions(
+    configuration: &Configuration,
+    ambiguous: bool,
+) -> anyhow::Result<()> {
+    if ambiguous && !configuration.experimental_parser {
+        bail!("Ambiguous mode is not supported for the Zeitwerk parser");
+    }
+    let constant_resolver = get_constant_resolver(configuration)?;
 
     let constant_definition_map = constant_resolver
         .fully_qualified_constant_name_to_constant_definition_map();
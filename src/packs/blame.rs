@@ -0,0 +1,240 @@
+use std::{collections::HashMap, fmt, path::Path, process::Command};
+
+use anyhow::Context;
+use serde::Serialize;
+
+use super::date::Date;
+
+// Attribution of a single line of code to the commit that last touched it,
+// as reported by `git blame`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize)]
+pub struct Blame {
+    pub commit: String,
+    pub author: String,
+    pub date: String,
+}
+
+const UNCOMMITTED_SHA: &str = "0000000000000000000000000000000000000000";
+
+impl Blame {
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(8)]
+    }
+
+    pub fn is_committed(&self) -> bool {
+        self.commit != UNCOMMITTED_SHA
+    }
+}
+
+impl fmt::Display for Blame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_committed() {
+            write!(
+                f,
+                "Introduced in {} by {} on {}",
+                self.short_commit(),
+                self.author,
+                self.date
+            )
+        } else {
+            write!(f, "Introduced by uncommitted changes")
+        }
+    }
+}
+
+// Runs `git blame` for the given lines of a file in the repository containing
+// `absolute_root`, keyed by line. Lines git cannot attribute, e.g. because the file is
+// untracked, are left out. All lines are blamed at once, unless git fails to blame one
+// of them.
+pub fn blame_lines(
+    absolute_root: &Path,
+    relative_file: &str,
+    lines: &[usize],
+) -> anyhow::Result<HashMap<usize, Blame>> {
+    // ERB files do not have source maps, so their references have no line number. Lines
+    // past the end of the file are also left out, since git rejects every range then.
+    let line_count = std::fs::read_to_string(absolute_root.join(relative_file))
+        .map_or(usize::MAX, |contents| contents.lines().count());
+    let mut lines: Vec<usize> = lines
+        .iter()
+        .copied()
+        .filter(|line| (1..=line_count).contains(line))
+        .collect();
+    if lines.is_empty() {
+        return Ok(HashMap::new());
+    }
+    lines.sort();
+    lines.dedup();
+
+    if let Some(porcelain) = git_blame(absolute_root, relative_file, &lines)? {
+        return Ok(parse_porcelain(&porcelain));
+    }
+    if lines.len() == 1 {
+        return Ok(HashMap::new());
+    }
+
+    // Blame the lines one at a time, so that one line git can't blame doesn't drop the others
+    let mut blames = HashMap::new();
+    for line in lines {
+        if let Some(porcelain) =
+            git_blame(absolute_root, relative_file, &[line])?
+        {
+            blames.extend(parse_porcelain(&porcelain));
+        }
+    }
+    Ok(blames)
+}
+
+// The porcelain output of `git blame` for the given lines, or None when git fails
+fn git_blame(
+    absolute_root: &Path,
+    relative_file: &str,
+    lines: &[usize],
+) -> anyhow::Result<Option<String>> {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(absolute_root)
+        .arg("blame")
+        .arg("--porcelain");
+    for line in lines {
+        command.arg("-L").arg(format!("{},{}", line, line));
+    }
+    let output = command
+        .arg("--")
+        .arg(relative_file)
+        .output()
+        .context("Failed to run `git blame`. Is git installed?")?;
+
+    Ok(output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+// Each blamed line starts with a `<commit> <original line> <final line>` header and ends
+// with the line's contents, prefixed by a tab. The author of a commit is only included
+// the first time the commit appears.
+fn parse_porcelain(porcelain: &str) -> HashMap<usize, Blame> {
+    let mut authors: HashMap<&str, (&str, i64)> = HashMap::new();
+    let mut commits_by_line: Vec<(usize, &str)> = vec![];
+
+    let mut header: Option<(&str, usize)> = None;
+    let mut author = None;
+    let mut author_time = None;
+    for line in porcelain.lines() {
+        let Some((commit, final_line)) = header else {
+            let mut fields = line.split(' ');
+            let commit = fields.next();
+            let final_line = fields.nth(1).and_then(|l| l.parse().ok());
+            header = commit.zip(final_line);
+            continue;
+        };
+
+        if line.starts_with('\t') {
+            if let (Some(author), Some(author_time)) = (author, author_time) {
+                authors.insert(commit, (author, author_time));
+            }
+            commits_by_line.push((final_line, commit));
+            (header, author, author_time) = (None, None, None);
+        } else if let Some(name) = line.strip_prefix("author ") {
+            author = Some(name);
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            author_time = time.parse::<i64>().ok();
+        }
+    }
+
+    commits_by_line
+        .into_iter()
+        .filter_map(|(line, commit)| {
+            let (author, author_time) = authors.get(commit)?;
+            Some((
+                line,
+                Blame {
+                    commit: commit.to_owned(),
+                    author: (*author).to_owned(),
+                    date: Date::from_unix_timestamp(*author_time).to_string(),
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let porcelain = "\
+8f2c1d0e9b7a6f5e4d3c2b1a0f9e8d7c6b5a4f3e 3 3 1
+author Jane Doe
+author-mail <jane@example.com>
+author-time 1700000000
+author-tz +0000
+committer Jane Doe
+committer-mail <jane@example.com>
+committer-time 1700000000
+committer-tz +0000
+summary Add Foo
+filename packs/foo/app/services/foo.rb
+\tBar
+0000000000000000000000000000000000000000 5 5 1
+author Not Committed Yet
+author-mail <not.committed.yet>
+author-time 1709164800
+author-tz +0000
+committer Not Committed Yet
+committer-mail <not.committed.yet>
+committer-time 1709164800
+committer-tz +0000
+summary Version of packs/foo/app/services/foo.rb from packs/foo/app/services/foo.rb
+previous 8f2c1d0e9b7a6f5e4d3c2b1a0f9e8d7c6b5a4f3e packs/foo/app/services/foo.rb
+filename packs/foo/app/services/foo.rb
+\tBaz
+8f2c1d0e9b7a6f5e4d3c2b1a0f9e8d7c6b5a4f3e 7 9 1
+filename packs/foo/app/services/foo.rb
+\tQux
+";
+
+        let blames = parse_porcelain(porcelain);
+        assert_eq!(blames.len(), 3);
+        let blame = &blames[&3];
+        assert_eq!(
+            blame,
+            &Blame {
+                commit: "8f2c1d0e9b7a6f5e4d3c2b1a0f9e8d7c6b5a4f3e".to_owned(),
+                author: "Jane Doe".to_owned(),
+                date: "2023-11-14".to_owned(),
+            }
+        );
+        // A commit's author is only listed the first time it appears
+        assert_eq!(&blames[&9], blame);
+        assert!(!blames[&5].is_committed());
+        assert_eq!(
+            blame.to_string(),
+            "Introduced in 8f2c1d0e by Jane Doe on 2023-11-14"
+        );
+    }
+
+    #[test]
+    fn test_blame_lines_past_the_end_of_the_file() -> anyhow::Result<()> {
+        let blames = blame_lines(
+            Path::new("tests/fixtures/simple_app"),
+            "packs/foo/app/services/foo.rb",
+            &[3, 10_000],
+        )?;
+        assert_eq!(blames.keys().collect::<Vec<_>>(), vec![&3]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_uncommitted_blame() {
+        let blame = Blame {
+            commit: UNCOMMITTED_SHA.to_owned(),
+            author: "Not Committed Yet".to_owned(),
+            date: "2024-02-29".to_owned(),
+        };
+        assert_eq!(blame.to_string(), "Introduced by uncommitted changes");
+    }
+}
@@ -12,6 +12,10 @@ use super::cache::Cache;
 use super::CacheResult;
 use super::EmptyCacheEntry;
 
+// Bumped whenever processing starts extracting something new from files (e.g.
+// suppression comments), so that older cache entries are processed again.
+const CACHE_VERSION: usize = 2;
+
 pub struct PerFileCache {
     pub cache_dir: PathBuf,
 }
@@ -25,7 +29,8 @@ impl Cache for PerFileCache {
             let file_digests_match = cache_entry.file_contents_digest
                 == empty_cache_entry.file_contents_digest;
 
-            if !file_digests_match {
+            if !file_digests_match || cache_entry.cache_version != CACHE_VERSION
+            {
                 Ok(CacheResult::Miss(empty_cache_entry))
             } else {
                 let processed_file = cache_entry.processed_file;
@@ -46,6 +51,7 @@ impl Cache for PerFileCache {
 
         let cache_entry = &CacheEntry {
             file_contents_digest,
+            cache_version: CACHE_VERSION,
             // Ideally we could pass by reference here, but in practice this cost should be paid on few files
             // that have changed and need to be reprocessed.
             processed_file: processed_file.clone(),
@@ -70,6 +76,9 @@ impl Cache for PerFileCache {
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CacheEntry {
     pub file_contents_digest: String,
+    // Entries written by packwerk or older versions have no version (0)
+    #[serde(default)]
+    pub cache_version: usize,
     pub processed_file: ProcessedFile,
 }
 
@@ -158,6 +167,7 @@ mod tests {
 
         let expected_serialized = CacheEntry {
             file_contents_digest: "8f9efdcf2caa22fb7b1b4a8274e68d11".to_owned(),
+            cache_version: 0,
             processed_file: ProcessedFile {
                 absolute_path: PathBuf::from("/tests/fixtures/simple_app/packs/foo/app/services/bar/foo.rb"),
                 unresolved_references: vec![UnresolvedReference {
@@ -172,6 +182,7 @@ mod tests {
                 }],
                 definitions: vec![],
                 sigils: vec![],
+                suppressions: vec![],
             }
         };
 
@@ -197,7 +208,7 @@ mod tests {
         fs::create_dir_all(&cache_path)
             .context("unable to create cache dir")?;
         let corrupt_file_path = cache_path.join(sha);
-        fs::write(corrupt_file_path, corrupt_contents)
+        fs::write(&corrupt_file_path, corrupt_contents)
             .context("expected to write corrupt cache file")?;
 
         let empty_cache_entry = EmptyCacheEntry::new(
@@ -207,8 +218,10 @@ mod tests {
             ),
         ).context("expected tests/fixtures/simple_app/packs/foo/app/services/foo/bar.rb to exist")?;
 
-        let entry = CacheEntry::from_empty(&empty_cache_entry)?;
-        assert!(entry.is_none());
+        let entry = CacheEntry::from_empty(&empty_cache_entry);
+        fs::remove_file(&corrupt_file_path)
+            .context("expected to remove corrupt cache file")?;
+        assert!(entry?.is_none());
 
         Ok(())
     }
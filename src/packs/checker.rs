@@ -1,20 +1,34 @@
 // Module declarations
+pub(crate) mod architecture_rule;
+mod budget;
 mod dependency;
+mod expiry;
 pub(crate) mod layer;
 
 mod common_test;
 mod folder_privacy;
+pub(crate) mod forbidden_constant;
+mod gem_dependency;
+mod json;
+mod markdown;
 mod output_helper;
 pub(crate) mod pack_checker;
 mod privacy;
+mod promote;
+mod public_paths;
 pub(crate) mod reference;
+mod suppression;
 mod visibility;
 
 // Internal imports
+use crate::packs::configuration::OutputFormat;
+use crate::packs::file_utils::user_inputted_paths_to_absolute_filepaths;
 use crate::packs::pack::write_pack_to_disk;
 use crate::packs::pack::Pack;
 use crate::packs::package_todo;
+use crate::packs::package_todo::CarriedOverViolation;
 use crate::packs::Configuration;
+use crate::packs::SourceLocation;
 
 use anyhow::bail;
 use anyhow::Context;
@@ -23,6 +37,8 @@ use rayon::prelude::IntoParallelIterator;
 use rayon::prelude::IntoParallelRefIterator;
 use rayon::prelude::ParallelIterator;
 use reference::Reference;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Display;
@@ -31,22 +47,78 @@ use std::{collections::HashSet, path::PathBuf};
 use tracing::debug;
 
 use super::bin_locater;
+use super::blame::{blame_lines, Blame};
 use super::reference_extractor::get_all_references_and_sigils;
 use super::Sigil;
+use budget::BudgetOverrun;
+use suppression::UnusedSuppression;
 
-#[derive(PartialEq, Clone, Eq, Hash, Debug)]
+#[derive(PartialEq, Clone, Eq, Hash, Debug, Serialize)]
 pub struct ViolationIdentifier {
     pub violation_type: String,
     pub strict: bool,
+    // Violations of checkers set to `warn` are reported, but never fail `check` or get recorded
+    pub warning: bool,
     pub file: String,
     pub constant_name: String,
     pub referencing_pack_name: String,
     pub defining_pack_name: String,
 }
+#[derive(PartialEq, Clone, Copy, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleReason {
+    FileDeleted,
+    // The file exists, but is excluded from checks, e.g. by `exclude` in packwerk.yml
+    FileNotIncluded,
+    ViolationFixed,
+}
+
+impl Display for StaleReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StaleReason::FileDeleted => write!(f, "the file was deleted"),
+            StaleReason::FileNotIncluded => {
+                write!(f, "the file is no longer included in checks")
+            }
+            StaleReason::ViolationFixed => write!(f, "the violation was fixed"),
+        }
+    }
+}
+
+// A violation recorded in a package_todo.yml file that no longer occurs
+#[derive(PartialEq, Clone, Eq, Debug, Serialize)]
+pub struct StaleViolation {
+    #[serde(flatten)]
+    pub identifier: ViolationIdentifier,
+    pub reason: StaleReason,
+}
+
+impl Display for StaleViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} has a recorded {} violation on `{}` (defined in `{}`) in {}, but {}",
+            self.identifier.referencing_pack_name,
+            self.identifier.violation_type,
+            self.identifier.constant_name,
+            self.identifier.defining_pack_name,
+            self.identifier.file,
+            self.reason,
+        )
+    }
+}
+
 #[derive(PartialEq, Clone, Eq, Hash, Debug)]
 pub struct Violation {
     message: String,
     pub identifier: ViolationIdentifier,
+    source_location: SourceLocation,
+}
+
+impl Violation {
+    fn location_key(&self) -> (String, usize) {
+        (self.identifier.file.clone(), self.source_location.line)
+    }
 }
 
 pub(crate) trait CheckerInterface {
@@ -67,15 +139,35 @@ pub(crate) trait ValidatorInterface {
 #[derive(Debug, PartialEq)]
 pub struct CheckAllResult {
     reportable_violations: HashSet<Violation>,
-    stale_violations: Vec<ViolationIdentifier>,
+    stale_violations: Vec<StaleViolation>,
     strict_mode_violations: Vec<ViolationIdentifier>,
+    // Keyed by (relative file, line). Only populated when running `check --blame`.
+    blames: HashMap<(String, usize), Blame>,
+    // Only populated when running `check --ratchet`.
+    budget_overruns: Vec<BudgetOverrun>,
+    // Expired or soon to expire todo deadlines and enforcement_globs_ignore entries.
+    // These are informational and do not fail `check`.
+    expiry_warnings: Vec<String>,
+    // Violations from checkers set to `warn`. Reported, but do not fail `check`.
+    warning_violations: Vec<Violation>,
+    unused_suppressions: Vec<UnusedSuppression>,
 }
 
 impl CheckAllResult {
+    pub fn to_markdown(&self) -> String {
+        markdown::check_report(self)
+    }
+
+    pub fn to_json(&self) -> String {
+        json::check_report(self)
+    }
+
     pub fn has_violations(&self) -> bool {
         !self.reportable_violations.is_empty()
             || !self.stale_violations.is_empty()
             || !self.strict_mode_violations.is_empty()
+            || !self.budget_overruns.is_empty()
+            || !self.unused_suppressions.is_empty()
     }
 
     fn write_violations(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -86,8 +178,17 @@ impl CheckAllResult {
 
             writeln!(f, "{} violation(s) detected:", sorted_violations.len())?;
 
-            for violation in sorted_violations {
-                writeln!(f, "{}\n", violation.message)?;
+            for violation in &sorted_violations {
+                match self.blames.get(&violation.location_key()) {
+                    Some(blame) => {
+                        writeln!(f, "{}\n{}\n", violation.message, blame)?
+                    }
+                    None => writeln!(f, "{}\n", violation.message)?,
+                }
+            }
+
+            if !self.blames.is_empty() {
+                self.write_violations_by_author(f)?;
             }
         }
 
@@ -97,6 +198,16 @@ impl CheckAllResult {
                 "There were stale violations found, please run `{} update`",
                 bin_locater::packs_bin_name(),
             )?;
+
+            let mut stale_messages: Vec<String> = self
+                .stale_violations
+                .iter()
+                .map(|v| v.to_string())
+                .collect();
+            stale_messages.sort();
+            for message in stale_messages {
+                writeln!(f, "- {}", message)?;
+            }
         }
 
         if !self.strict_mode_violations.is_empty() {
@@ -105,12 +216,76 @@ impl CheckAllResult {
                 writeln!(f, "{}", error_message)?;
             }
         }
+
+        for overrun in &self.budget_overruns {
+            writeln!(f, "{}", overrun)?;
+        }
+
+        if !self.unused_suppressions.is_empty() {
+            writeln!(
+                f,
+                "There were unused suppressions found, please remove them"
+            )?;
+            for unused in &self.unused_suppressions {
+                writeln!(f, "- {}", unused)?;
+            }
+        }
         Ok(())
     }
+
+    fn write_violations_by_author(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "New violations by author:")?;
+        for (author, count) in self.violation_counts_by_author() {
+            writeln!(f, "- {}: {}", author, count)?;
+        }
+        writeln!(f)
+    }
+
+    // The number of new violations introduced by each author, most first. Only
+    // populated when running `check --blame`.
+    fn violation_counts_by_author(&self) -> Vec<(&str, usize)> {
+        if self.blames.is_empty() {
+            return vec![];
+        }
+        let mut counts_by_author: HashMap<&str, usize> = HashMap::new();
+        for violation in &self.reportable_violations {
+            let author = match self.blames.get(&violation.location_key()) {
+                Some(blame) if blame.is_committed() => blame.author.as_str(),
+                Some(_) => "Not Committed Yet",
+                None => "Unknown",
+            };
+            *counts_by_author.entry(author).or_default() += 1;
+        }
+
+        let mut sorted_counts: Vec<(&str, usize)> =
+            counts_by_author.into_iter().collect();
+        sorted_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        sorted_counts
+    }
 }
 
 impl Display for CheckAllResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for warning in &self.expiry_warnings {
+            writeln!(f, "Warning: {}", warning)?;
+        }
+        if !self.expiry_warnings.is_empty() {
+            writeln!(f)?;
+        }
+
+        if !self.warning_violations.is_empty() {
+            let mut messages: Vec<&str> = self
+                .warning_violations
+                .iter()
+                .map(|v| v.message.as_str())
+                .collect();
+            messages.sort();
+            writeln!(f, "{} warning(s):", messages.len())?;
+            for message in messages {
+                writeln!(f, "{}\n", message)?;
+            }
+        }
+
         if self.has_violations() {
             self.write_violations(f)
         } else {
@@ -126,6 +301,8 @@ struct CheckAllBuilder<'a> {
 struct FoundViolations {
     absolute_paths: HashSet<PathBuf>,
     violations: HashSet<Violation>,
+    warnings: HashSet<Violation>,
+    unused_suppressions: Vec<UnusedSuppression>,
 }
 
 impl<'a> CheckAllBuilder<'a> {
@@ -141,26 +318,85 @@ impl<'a> CheckAllBuilder<'a> {
 
     pub fn build(mut self) -> anyhow::Result<CheckAllResult> {
         let recorded_violations = &self.configuration.pack_set.all_violations;
+        let reportable_violations =
+            self.build_reportable_violations(recorded_violations);
+        let blames = if self.configuration.blame_violations {
+            self.build_blames(&reportable_violations)?
+        } else {
+            HashMap::new()
+        };
 
         Ok(CheckAllResult {
-            reportable_violations: self
-                .build_reportable_violations(recorded_violations)
+            reportable_violations: reportable_violations
                 .into_iter()
                 .cloned()
                 .collect(),
             stale_violations: self
-                .build_stale_violations(recorded_violations)?
-                .into_iter()
-                .cloned()
-                .collect(),
+                .build_stale_violations(recorded_violations)?,
             strict_mode_violations: self
                 .build_strict_mode_violations()
                 .into_iter()
                 .cloned()
                 .collect(),
+            blames,
+            budget_overruns: self.build_budget_overruns(),
+            expiry_warnings: expiry::expiry_warnings(self.configuration),
+            warning_violations: self
+                .found_violations
+                .warnings
+                .iter()
+                .cloned()
+                .collect(),
+            unused_suppressions: self
+                .found_violations
+                .unused_suppressions
+                .clone(),
         })
     }
 
+    fn build_budget_overruns(&self) -> Vec<BudgetOverrun> {
+        if !self.configuration.ratchet {
+            return vec![];
+        }
+        self.configuration
+            .pack_set
+            .packs
+            .iter()
+            .flat_map(budget::overruns)
+            .collect()
+    }
+
+    fn build_blames(
+        &self,
+        reportable_violations: &HashSet<&'a Violation>,
+    ) -> anyhow::Result<HashMap<(String, usize), Blame>> {
+        debug!("Blaming reportable violations");
+        let mut lines_by_file: HashMap<String, Vec<usize>> = HashMap::new();
+        for violation in reportable_violations {
+            let (file, line) = violation.location_key();
+            lines_by_file.entry(file).or_default().push(line);
+        }
+
+        // One `git blame` per file, for all of its violation lines
+        let blames = lines_by_file
+            .into_par_iter()
+            .map(|(file, lines)| {
+                let blames = blame_lines(
+                    &self.configuration.absolute_root,
+                    &file,
+                    &lines,
+                )?;
+                Ok(blames
+                    .into_iter()
+                    .map(|(line, blame)| ((file.clone(), line), blame))
+                    .collect::<Vec<_>>())
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        debug!("Finished blaming reportable violations");
+
+        Ok(blames.into_iter().flatten().collect())
+    }
+
     fn build_reportable_violations(
         &mut self,
         recorded_violations: &HashSet<ViolationIdentifier>,
@@ -170,19 +406,72 @@ impl<'a> CheckAllBuilder<'a> {
                 debug!("Filtering recorded violations is disabled in config");
                 self.found_violations.violations.iter().collect()
             } else {
-                self.found_violations
+                let mut reportable_violations: HashSet<&'a Violation> = self
+                    .found_violations
                     .violations
                     .iter()
-                    .filter(|v| !recorded_violations.contains(&v.identifier))
-                    .collect()
+                    .filter(|v| {
+                        !recorded_violations.contains(&v.identifier)
+                            || self.is_todo_expired(&v.identifier)
+                    })
+                    .collect();
+                reportable_violations.extend(
+                    self.build_excess_occurrences(&reportable_violations),
+                );
+                reportable_violations
             };
         reportable_violations
     }
 
+    // References beyond the recorded occurrence count of a file. Which references were
+    // added is unknown, so the last ones in the file are reported.
+    fn build_excess_occurrences(
+        &self,
+        reportable_violations: &HashSet<&'a Violation>,
+    ) -> Vec<&'a Violation> {
+        let recorded_occurrences =
+            &self.configuration.pack_set.recorded_occurrences;
+        let mut violations_by_identifier: HashMap<
+            &ViolationIdentifier,
+            Vec<&'a Violation>,
+        > = HashMap::new();
+        for violation in &self.found_violations.violations {
+            if recorded_occurrences.contains_key(&violation.identifier)
+                && !reportable_violations.contains(violation)
+            {
+                violations_by_identifier
+                    .entry(&violation.identifier)
+                    .or_default()
+                    .push(violation);
+            }
+        }
+
+        violations_by_identifier
+            .into_iter()
+            .flat_map(|(identifier, mut violations)| {
+                violations
+                    .sort_by(|a, b| a.source_location.cmp(&b.source_location));
+                violations
+                    .into_iter()
+                    .skip(recorded_occurrences[identifier])
+            })
+            .collect()
+    }
+
+    fn is_todo_expired(
+        &self,
+        violation_identifier: &ViolationIdentifier,
+    ) -> bool {
+        let pack_set = &self.configuration.pack_set;
+        pack_set
+            .for_pack(pack_set.responsible_pack_name(violation_identifier))
+            .is_ok_and(|pack| pack.is_todo_expired(self.configuration.today))
+    }
+
     fn build_stale_violations(
         &mut self,
         recorded_violations: &'a HashSet<ViolationIdentifier>,
-    ) -> anyhow::Result<Vec<&'a ViolationIdentifier>> {
+    ) -> anyhow::Result<Vec<StaleViolation>> {
         let found_violation_identifiers: HashSet<&ViolationIdentifier> = self
             .found_violations
             .violations
@@ -216,28 +505,46 @@ impl<'a> CheckAllBuilder<'a> {
 
         let stale_violations = recorded_violations
             .par_iter()
-            .filter(|v_identifier| {
-                Self::is_stale_violation(
+            .filter_map(|v_identifier| {
+                self.stale_reason(
                     &relative_files,
                     &found_violation_identifiers,
                     v_identifier,
                 )
+                .map(|reason| StaleViolation {
+                    identifier: v_identifier.clone(),
+                    reason,
+                })
             })
-            .collect::<Vec<&ViolationIdentifier>>();
+            .collect::<Vec<StaleViolation>>();
         Ok(stale_violations)
     }
 
-    fn is_stale_violation(
+    // Why the recorded violation no longer occurs, or `None` if it still occurs or
+    // its file was not checked
+    fn stale_reason(
+        &self,
         relative_files: &HashSet<&str>,
         found_violation_identifiers: &HashSet<&ViolationIdentifier>,
         todo_violation_identifier: &ViolationIdentifier,
-    ) -> bool {
-        let violation_path_exists =
-            relative_files.contains(todo_violation_identifier.file.as_str());
-        if violation_path_exists {
-            !found_violation_identifiers.contains(todo_violation_identifier)
+    ) -> Option<StaleReason> {
+        if relative_files.contains(todo_violation_identifier.file.as_str()) {
+            return (!found_violation_identifiers
+                .contains(todo_violation_identifier))
+            .then_some(StaleReason::ViolationFixed);
+        }
+        if self.configuration.input_files_count > 0 {
+            // Only the given files were checked, so nothing is known about the others
+            return None;
+        }
+        let absolute_path = self
+            .configuration
+            .absolute_root
+            .join(&todo_violation_identifier.file);
+        if absolute_path.exists() {
+            Some(StaleReason::FileNotIncluded)
         } else {
-            true // The todo violation references a file that no longer exists
+            Some(StaleReason::FileDeleted)
         }
     }
 
@@ -255,17 +562,25 @@ pub(crate) fn check_all(
     configuration: &Configuration,
     files: Vec<String>,
 ) -> anyhow::Result<CheckAllResult> {
-    let checkers = get_checkers(configuration);
+    let checkers = get_checkers(configuration)?;
 
     debug!("Intersecting input files with configuration included files");
     let absolute_paths: HashSet<PathBuf> =
         configuration.intersect_files(files.clone());
 
-    let violations: HashSet<Violation> =
-        get_all_violations(configuration, &absolute_paths, &checkers)?;
+    let (violations, unused_suppressions) =
+        get_all_violations_and_unused_suppressions(
+            configuration,
+            &absolute_paths,
+            &checkers,
+        )?;
+    let (warnings, violations): (HashSet<Violation>, HashSet<Violation>) =
+        violations.into_iter().partition(|v| v.identifier.warning);
     let found_violations = FoundViolations {
         absolute_paths,
         violations,
+        warnings,
+        unused_suppressions,
     };
     CheckAllBuilder::new(configuration, &found_violations).build()
 }
@@ -277,6 +592,17 @@ fn validate(configuration: &Configuration) -> Vec<String> {
         Box::new(layer::Checker {
             layers: configuration.layers.clone(),
         }),
+        Box::new(privacy::Checker {}),
+        Box::new(visibility::Checker {}),
+        Box::new(folder_privacy::Checker {}),
+        Box::new(architecture_rule::Checker {
+            rules: configuration.architecture_rules.clone(),
+        }),
+        Box::new(forbidden_constant::Checker {
+            forbidden_constants: configuration.forbidden_constants.clone(),
+        }),
+        // The gem sources are only needed to check references
+        Box::new(gem_dependency::Checker::default()),
     ];
 
     let mut validation_errors: Vec<String> = validators
@@ -293,16 +619,27 @@ fn validate(configuration: &Configuration) -> Vec<String> {
 pub(crate) fn build_strict_violation_message(
     violation_identifier: &ViolationIdentifier,
 ) -> String {
-    format!("{} cannot have {} violations on {} because strict mode is enabled for {} violations in the enforcing pack's package.yml file",
+    // Rules declared in packwerk.yml have their own enforcement setting
+    let setting_location = match violation_identifier.violation_type.as_str() {
+        "architecture_rule" => "the matching architecture_rules entry",
+        "forbidden_constant" => "the matching forbidden_constants entry",
+        _ => "the enforcing pack's package.yml file",
+    };
+    format!("{} cannot have {} violations on {} because strict mode is enabled for {} violations in {}",
     violation_identifier.referencing_pack_name,
     violation_identifier.violation_type,
     violation_identifier.defining_pack_name,
-    violation_identifier.violation_type,)
+    violation_identifier.violation_type,
+    setting_location,)
 }
 
 pub(crate) fn validate_all(
     configuration: &Configuration,
 ) -> anyhow::Result<()> {
+    for warning in public_paths::unmatched_public_paths(configuration) {
+        println!("Warning: {}", warning);
+    }
+
     let validation_errors = validate(configuration);
     if !validation_errors.is_empty() {
         println!("{} validation error(s) detected:", validation_errors.len());
@@ -317,19 +654,235 @@ pub(crate) fn validate_all(
     }
 }
 
-pub(crate) fn update(configuration: &Configuration) -> anyhow::Result<()> {
-    let checkers = get_checkers(configuration);
+// The files `update` recomputes violations for, and the packs whose package_todo.yml
+// files it rewrites. Without any pack or file arguments, this is everything.
+struct UpdateScope {
+    absolute_paths: HashSet<PathBuf>,
+    // Only set when packs were passed in, in which case only files in these packs are checked
+    pack_names: HashSet<String>,
+    responsible_pack_names: HashSet<String>,
+    // Only set when files were passed in, in which case recorded violations in other files
+    // of the responsible packs are kept as they are.
+    relative_files: Option<HashSet<String>>,
+}
 
-    let violations = get_all_violations(
-        configuration,
-        &configuration.included_files,
-        &checkers,
-    )?;
+impl UpdateScope {
+    fn build(
+        configuration: &Configuration,
+        pack_names: Vec<String>,
+        files: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        let pack_set = &configuration.pack_set;
+        let pack_names = pack_names
+            .iter()
+            .map(|name| pack_set.for_pack(name).map(|p| p.name.clone()))
+            .collect::<anyhow::Result<HashSet<String>>>()?;
+
+        let relative_files = if files.is_empty() {
+            None
+        } else {
+            let absolute_paths = user_inputted_paths_to_absolute_filepaths(
+                &configuration.absolute_root,
+                files.clone(),
+            );
+            Some(
+                absolute_paths
+                    .iter()
+                    .filter_map(|p| {
+                        p.strip_prefix(&configuration.absolute_root).ok()
+                    })
+                    .filter_map(|p| p.to_str().map(String::from))
+                    .collect::<HashSet<String>>(),
+            )
+        };
+
+        let mut absolute_paths = HashSet::new();
+        let mut owning_pack_names = HashSet::new();
+        for path in configuration.intersect_files(files) {
+            let owning_pack_name =
+                pack_set.for_file(&path)?.map(|pack| pack.name.clone());
+            let in_scope = pack_names.is_empty()
+                || owning_pack_name
+                    .as_ref()
+                    .is_some_and(|name| pack_names.contains(name));
+            if in_scope {
+                owning_pack_names.extend(owning_pack_name);
+                absolute_paths.insert(path);
+            }
+        }
+
+        let responsible_pack_names = if !pack_names.is_empty() {
+            // Defining packs that record incoming violations from these packs need to
+            // be rewritten as well.
+            let mut responsible_pack_names = pack_names.clone();
+            responsible_pack_names.extend(
+                pack_set
+                    .all_violations
+                    .iter()
+                    .filter(|v| pack_names.contains(&v.referencing_pack_name))
+                    .map(|v| pack_set.responsible_pack_name(v).to_owned()),
+            );
+            responsible_pack_names
+        } else if let Some(relative_files) = &relative_files {
+            // Packs that recorded violations in files that are no longer owned by them
+            // (e.g. deleted files) need to be rewritten as well.
+            owning_pack_names.extend(
+                pack_set
+                    .all_violations
+                    .iter()
+                    .filter(|v| relative_files.contains(&v.file))
+                    .map(|v| pack_set.responsible_pack_name(v).to_owned()),
+            );
+            owning_pack_names
+        } else {
+            pack_set.packs.iter().map(|p| p.name.clone()).collect()
+        };
+
+        Ok(Self {
+            absolute_paths,
+            pack_names,
+            responsible_pack_names,
+            relative_files,
+        })
+    }
+
+    // Packs that record newly found violations, e.g. a defining pack that records
+    // incoming violations, are rewritten along with the packs in scope.
+    fn add_responsible_packs(
+        &mut self,
+        configuration: &Configuration,
+        violations: &HashSet<Violation>,
+    ) {
+        let pack_set = &configuration.pack_set;
+        self.responsible_pack_names.extend(
+            violations.iter().map(|v| {
+                pack_set.responsible_pack_name(&v.identifier).to_owned()
+            }),
+        );
+    }
+
+    // Whether the violation is recomputed, i.e. it is recorded by a responsible pack
+    // and occurs in a file that is checked
+    fn includes(
+        &self,
+        configuration: &Configuration,
+        violation_identifier: &ViolationIdentifier,
+    ) -> bool {
+        self.is_responsible_for(configuration, violation_identifier)
+            && self.relative_files.as_ref().map_or(true, |files| {
+                files.contains(&violation_identifier.file)
+            })
+            && (self.pack_names.is_empty()
+                || self
+                    .pack_names
+                    .contains(&violation_identifier.referencing_pack_name))
+    }
+
+    fn is_responsible_for(
+        &self,
+        configuration: &Configuration,
+        violation_identifier: &ViolationIdentifier,
+    ) -> bool {
+        self.responsible_pack_names.contains(
+            configuration
+                .pack_set
+                .responsible_pack_name(violation_identifier),
+        )
+    }
+
+    // Recorded violations of the responsible packs that are not being recomputed, with
+    // their occurrence counts
+    fn carried_over_violations(
+        &self,
+        configuration: &Configuration,
+    ) -> Vec<CarriedOverViolation> {
+        let pack_set = &configuration.pack_set;
+        pack_set
+            .all_violations
+            .iter()
+            .filter(|v| {
+                self.is_responsible_for(configuration, v)
+                    && !self.includes(configuration, v)
+            })
+            .map(|v| (v.clone(), pack_set.recorded_occurrences.get(v).copied()))
+            .collect()
+    }
+}
+
+pub(crate) fn update(
+    configuration: &Configuration,
+    pack_names: Vec<String>,
+    files: Vec<String>,
+) -> anyhow::Result<()> {
+    if configuration.promote_strict
+        && (!pack_names.is_empty() || !files.is_empty())
+    {
+        // Promotion needs the violations of every file, not just those in scope
+        bail!("--promote-strict cannot be combined with --pack or files");
+    }
+    let checkers = get_checkers(configuration)?;
+    let mut scope = UpdateScope::build(configuration, pack_names, files)?;
+
+    let mut violations =
+        get_all_violations(configuration, &scope.absolute_paths, &checkers)?;
+    // Warnings are only reported by `check`; they are never recorded.
+    violations.retain(|v| !v.identifier.warning);
+    scope.add_responsible_packs(configuration, &violations);
+
+    if configuration.prune_only {
+        // Only keep what is already recorded, so stale entries are removed
+        // without recording any new violations.
+        let recorded_violations = &configuration.pack_set.all_violations;
+        violations.retain(|v| recorded_violations.contains(&v.identifier));
+        if configuration.todo_occurrence_counts {
+            violations = without_excess_occurrences(configuration, violations);
+        }
+    }
+    let carried_over = scope.carried_over_violations(configuration);
+
+    if configuration.dry_run {
+        let diff = package_todo::diff_violations_with_disk(
+            configuration,
+            violations,
+            &carried_over,
+            &scope.responsible_pack_names,
+        );
+        match configuration.output_format {
+            OutputFormat::Text => println!("{}", diff),
+            OutputFormat::Markdown => {
+                println!("{}", markdown::dry_run_report(&diff))
+            }
+            OutputFormat::Json => println!("{}", json::dry_run_report(&diff)),
+        }
+        if diff.has_additions() {
+            bail!(
+                "New violations are not recorded in package_todo.yml files, please run `{} update`",
+                bin_locater::packs_bin_name()
+            );
+        }
+        return Ok(());
+    }
 
     let strict_violations = &violations
         .iter()
         .filter(|v| v.identifier.strict)
         .collect::<Vec<&Violation>>();
+    if configuration.output_format != OutputFormat::Text {
+        let reported_violations =
+            violations.iter().collect::<Vec<&Violation>>();
+        let report = match configuration.output_format {
+            OutputFormat::Json => json::update_report(&reported_violations),
+            _ => markdown::update_report(&reported_violations),
+        };
+        println!("{}", report);
+        write_violations_and_lower_budgets(
+            configuration,
+            violations,
+            &carried_over,
+            &scope,
+        )?;
+        return Ok(());
+    }
     if !strict_violations.is_empty() {
         for violation in strict_violations {
             let strict_message =
@@ -341,12 +894,131 @@ pub(crate) fn update(configuration: &Configuration) -> anyhow::Result<()> {
             &strict_violations.len()
         );
     }
-    package_todo::write_violations_to_disk(configuration, violations);
+    write_violations_and_lower_budgets(
+        configuration,
+        violations,
+        &carried_over,
+        &scope,
+    )?;
     println!("Successfully updated package_todo.yml files!");
 
     Ok(())
 }
 
+// Drops references beyond the recorded occurrence count of a file, so that recorded
+// counts can go down but never up. As in `check`, the last references in the file are dropped.
+fn without_excess_occurrences(
+    configuration: &Configuration,
+    violations: HashSet<Violation>,
+) -> HashSet<Violation> {
+    let recorded_occurrences = &configuration.pack_set.recorded_occurrences;
+    let mut violations_by_identifier: HashMap<
+        ViolationIdentifier,
+        Vec<Violation>,
+    > = HashMap::new();
+    for violation in violations {
+        violations_by_identifier
+            .entry(violation.identifier.clone())
+            .or_default()
+            .push(violation);
+    }
+
+    violations_by_identifier
+        .into_iter()
+        .flat_map(|(identifier, mut violations)| {
+            violations
+                .sort_by(|a, b| a.source_location.cmp(&b.source_location));
+            let recorded_occurrences = recorded_occurrences
+                .get(&identifier)
+                .copied()
+                .unwrap_or(usize::MAX);
+            violations.into_iter().take(recorded_occurrences)
+        })
+        .collect()
+}
+
+fn write_violations_and_lower_budgets(
+    configuration: &Configuration,
+    violations: HashSet<Violation>,
+    carried_over: &[CarriedOverViolation],
+    scope: &UpdateScope,
+) -> anyhow::Result<()> {
+    let live_violations = if configuration.promote_strict {
+        violations.clone()
+    } else {
+        HashSet::new()
+    };
+    let package_todos_by_pack_name = package_todo::write_violations_to_disk(
+        configuration,
+        violations,
+        carried_over,
+        &scope.responsible_pack_names,
+    )?;
+    // Both the lowered budgets and the promotions change package.yml files, so each
+    // pack is written once with all of its changes.
+    let mut updated_packs = BTreeMap::new();
+    let lowered_pack_names = budget::lower_budgets(
+        configuration,
+        &package_todos_by_pack_name,
+        &scope.responsible_pack_names,
+        &mut updated_packs,
+    );
+    let promotions = if configuration.promote_strict {
+        promote::promote_strict(
+            configuration,
+            &package_todos_by_pack_name,
+            &scope.responsible_pack_names,
+            &live_violations,
+            &mut updated_packs,
+        )
+    } else {
+        vec![]
+    };
+    for pack in updated_packs.values() {
+        write_pack_to_disk(pack)?;
+    }
+
+    for pack_name in lowered_pack_names {
+        println!(
+            "Lowered the violation_budget of {} to match its package_todo.yml",
+            pack_name
+        );
+    }
+    for promotion in promotions {
+        println!("{}", promotion);
+    }
+    Ok(())
+}
+
+pub(crate) fn promote_strict(
+    configuration: &Configuration,
+) -> anyhow::Result<()> {
+    let checkers = get_checkers(configuration)?;
+    let violations = get_all_violations(
+        configuration,
+        &configuration.included_files,
+        &checkers,
+    )?;
+    let mut updated_packs = BTreeMap::new();
+    let promotions = promote::promote_strict(
+        configuration,
+        &HashMap::new(),
+        &HashSet::new(),
+        &violations,
+        &mut updated_packs,
+    );
+    for pack in updated_packs.values() {
+        write_pack_to_disk(pack)?;
+    }
+    if promotions.is_empty() {
+        println!("No packs can be promoted to strict");
+    }
+    for promotion in promotions {
+        println!("{}", promotion);
+    }
+    Ok(())
+}
+
 pub(crate) fn remove_unnecessary_dependencies(
     configuration: &Configuration,
 ) -> anyhow::Result<()> {
@@ -361,7 +1033,7 @@ pub(crate) fn add_all_dependencies(
     configuration: &Configuration,
     pack_name: &str,
 ) -> anyhow::Result<()> {
-    let (references, _sigils) = get_all_references_and_sigils(
+    let (references, _sigils, _suppressions) = get_all_references_and_sigils(
         configuration,
         &configuration.included_files,
     )?;
@@ -425,7 +1097,7 @@ pub(crate) fn check_unnecessary_dependencies(
 fn get_unnecessary_dependencies(
     configuration: &Configuration,
 ) -> anyhow::Result<HashMap<Pack, Vec<String>>> {
-    let (references, _sigils) = get_all_references_and_sigils(
+    let (references, _sigils, _suppressions) = get_all_references_and_sigils(
         configuration,
         &configuration.included_files,
     )?;
@@ -466,11 +1138,25 @@ fn get_all_violations(
     absolute_paths: &HashSet<PathBuf>,
     checkers: &Vec<Box<dyn CheckerInterface + Send + Sync>>,
 ) -> anyhow::Result<HashSet<Violation>> {
-    let (references, sigils) =
+    let (violations, _unused_suppressions) =
+        get_all_violations_and_unused_suppressions(
+            configuration,
+            absolute_paths,
+            checkers,
+        )?;
+    Ok(violations)
+}
+
+fn get_all_violations_and_unused_suppressions(
+    configuration: &Configuration,
+    absolute_paths: &HashSet<PathBuf>,
+    checkers: &Vec<Box<dyn CheckerInterface + Send + Sync>>,
+) -> anyhow::Result<(HashSet<Violation>, Vec<UnusedSuppression>)> {
+    let (references, sigils, suppressions) =
         get_all_references_and_sigils(configuration, absolute_paths)?;
     debug!("Running checkers on resolved references");
 
-    let violations = checkers
+    let violations: anyhow::Result<HashSet<Violation>> = checkers
         .into_par_iter()
         .try_fold(HashSet::new, |mut acc, c| {
             for reference in &references {
@@ -489,13 +1175,17 @@ fn get_all_violations(
 
     debug!("Finished running checkers");
 
-    violations
+    Ok(suppression::apply_suppressions(
+        configuration,
+        violations?,
+        &suppressions,
+    ))
 }
 
 fn get_checkers(
     configuration: &Configuration,
-) -> Vec<Box<dyn CheckerInterface + Send + Sync>> {
-    vec![
+) -> anyhow::Result<Vec<Box<dyn CheckerInterface + Send + Sync>>> {
+    Ok(vec![
         Box::new(dependency::Checker {}),
         Box::new(privacy::Checker {}),
         Box::new(visibility::Checker {}),
@@ -503,7 +1193,14 @@ fn get_checkers(
             layers: configuration.layers.clone(),
         }),
         Box::new(folder_privacy::Checker {}),
-    ]
+        Box::new(architecture_rule::Checker {
+            rules: configuration.architecture_rules.clone(),
+        }),
+        Box::new(forbidden_constant::Checker {
+            forbidden_constants: configuration.forbidden_constants.clone(),
+        }),
+        Box::new(gem_dependency::Checker::build(configuration)?),
+    ])
 }
 
 fn remove_reference_to_dependency(
@@ -523,9 +1220,13 @@ fn remove_reference_to_dependency(
 }
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use crate::packs::blame::Blame;
     use crate::packs::checker::{
         CheckAllResult, Violation, ViolationIdentifier,
     };
+    use crate::packs::SourceLocation;
 
     #[test]
     fn test_write_violations() {
@@ -536,26 +1237,35 @@ mod tests {
                     identifier: ViolationIdentifier {
                         violation_type: "Privacy".to_string(),
                         strict: false,
+                        warning: false,
                         file: "foo/bar/file1.rb".to_string(),
                         constant_name: "::Foo::PrivateClass".to_string(),
                         referencing_pack_name: "bar".to_string(),
                         defining_pack_name: "foo".to_string(),
-                    }
+                    },
+                    source_location: SourceLocation { line: 10, column: 5 },
                 },
                 Violation {
                     message: "foo/bar/file2.rb:15:3\nDependency violation: `::Foo::AnotherClass` is not allowed to depend on `::Bar::SomeClass`".to_string(),
                     identifier: ViolationIdentifier {
                         violation_type: "Dependency".to_string(),
                         strict: false,
+                        warning: false,
                         file: "foo/bar/file2.rb".to_string(),
                         constant_name: "::Foo::AnotherClass".to_string(),
                         referencing_pack_name: "foo".to_string(),
                         defining_pack_name: "bar".to_string(),
-                    }
+                    },
+                    source_location: SourceLocation { line: 15, column: 3 },
                 }
             ].iter().cloned().collect(),
             stale_violations: Vec::new(),
             strict_mode_violations: Vec::new(),
+            blames: HashMap::new(),
+            budget_overruns: Vec::new(),
+            expiry_warnings: Vec::new(),
+            warning_violations: Vec::new(),
+            unused_suppressions: Vec::new(),
         };
 
         let expected_output = "2 violation(s) detected:
@@ -565,6 +1275,54 @@ Privacy violation: `::Foo::PrivateClass` is private to `foo`, but referenced fro
 foo/bar/file2.rb:15:3
 Dependency violation: `::Foo::AnotherClass` is not allowed to depend on `::Bar::SomeClass`
 
+";
+
+        let actual = format!("{}", chec_result);
+
+        assert_eq!(actual, expected_output);
+    }
+
+    #[test]
+    fn test_write_violations_with_blames() {
+        let violation = Violation {
+            message: "foo/bar/file1.rb:10:5\nPrivacy violation: `::Foo::PrivateClass` is private to `foo`, but referenced from `bar`".to_string(),
+            identifier: ViolationIdentifier {
+                violation_type: "Privacy".to_string(),
+                strict: false,
+                warning: false,
+                file: "foo/bar/file1.rb".to_string(),
+                constant_name: "::Foo::PrivateClass".to_string(),
+                referencing_pack_name: "bar".to_string(),
+                defining_pack_name: "foo".to_string(),
+            },
+            source_location: SourceLocation { line: 10, column: 5 },
+        };
+        let chec_result = CheckAllResult {
+            reportable_violations: vec![violation].into_iter().collect(),
+            stale_violations: Vec::new(),
+            strict_mode_violations: Vec::new(),
+            blames: HashMap::from([(
+                ("foo/bar/file1.rb".to_string(), 10),
+                Blame {
+                    commit: "8f2c1d0e9b7a6f5e4d3c".to_string(),
+                    author: "Jane Doe".to_string(),
+                    date: "2023-11-14".to_string(),
+                },
+            )]),
+            budget_overruns: Vec::new(),
+            expiry_warnings: Vec::new(),
+            warning_violations: Vec::new(),
+            unused_suppressions: Vec::new(),
+        };
+
+        let expected_output = "1 violation(s) detected:
+foo/bar/file1.rb:10:5
+Privacy violation: `::Foo::PrivateClass` is private to `foo`, but referenced from `bar`
+Introduced in 8f2c1d0e by Jane Doe on 2023-11-14
+
+New violations by author:
+- Jane Doe: 1
+
 ";
 
         let actual = format!("{}", chec_result);
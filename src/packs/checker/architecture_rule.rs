@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::output_helper::print_reference_location;
+use super::pack_checker::{
+    enforced_pack_dependencies, PackChecker, ViolationType,
+};
+use super::{CheckerInterface, ValidatorInterface};
+use crate::packs::checker::Reference;
+use crate::packs::ignored;
+use crate::packs::pack::{
+    deserialize_checker_setting, serialize_checker_setting, CheckerSetting,
+};
+use crate::packs::raw_configuration::string_or_vec;
+use crate::packs::{Configuration, Violation};
+
+// A rule between groups of packs, declared in `architecture_rules`. Each pattern matches
+// pack names, and the packs nested within them.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct ArchitectureRule {
+    #[serde(deserialize_with = "string_or_vec")]
+    pub packs: Vec<String>,
+
+    // `packs` may not reference these packs
+    #[serde(
+        default,
+        deserialize_with = "string_or_vec",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub may_not_depend_on: Vec<String>,
+
+    // Only these packs may reference `packs`
+    #[serde(
+        default,
+        deserialize_with = "string_or_vec",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub may_only_be_referenced_by: Vec<String>,
+
+    // How violations of the rule are enforced, e.g. `warn` or `strict`. Defaults to
+    // `true`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_checker_setting",
+        deserialize_with = "deserialize_checker_setting"
+    )]
+    pub enforcement: Option<CheckerSetting>,
+}
+
+fn matches_any(patterns: &[String], pack_name: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| ignored::is_match_or_within(pattern, pack_name))
+}
+
+fn patterns_description(patterns: &[String]) -> String {
+    patterns
+        .iter()
+        .map(|pattern| format!("`{}`", pattern))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl ArchitectureRule {
+    fn enforcement(&self) -> &CheckerSetting {
+        self.enforcement.as_ref().unwrap_or(&CheckerSetting::True)
+    }
+
+    fn forbids(
+        &self,
+        referencing_pack_name: &str,
+        defining_pack_name: &str,
+    ) -> bool {
+        if !self.may_not_depend_on.is_empty()
+            && matches_any(&self.packs, referencing_pack_name)
+            && matches_any(&self.may_not_depend_on, defining_pack_name)
+        {
+            return true;
+        }
+        !self.may_only_be_referenced_by.is_empty()
+            && matches_any(&self.packs, defining_pack_name)
+            && !matches_any(&self.packs, referencing_pack_name)
+            && !matches_any(
+                &self.may_only_be_referenced_by,
+                referencing_pack_name,
+            )
+    }
+
+    fn validate(&self) -> Option<String> {
+        let has_one_restriction = self.may_not_depend_on.is_empty()
+            != self.may_only_be_referenced_by.is_empty();
+        if self.packs.is_empty() || !has_one_restriction {
+            Some(format!(
+                "Invalid architecture rule for {}. A rule needs `packs` and exactly one of `may_not_depend_on` or `may_only_be_referenced_by`",
+                patterns_description(&self.packs)
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for ArchitectureRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.may_not_depend_on.is_empty() {
+            write!(
+                f,
+                "{} may only be referenced by {}",
+                patterns_description(&self.packs),
+                patterns_description(&self.may_only_be_referenced_by)
+            )
+        } else {
+            write!(
+                f,
+                "{} may not depend on {}",
+                patterns_description(&self.packs),
+                patterns_description(&self.may_not_depend_on)
+            )
+        }
+    }
+}
+
+pub struct Checker {
+    pub rules: Vec<ArchitectureRule>,
+}
+
+impl Checker {
+    fn forbidding_rule(
+        &self,
+        referencing_pack_name: &str,
+        defining_pack_name: &str,
+    ) -> Option<&ArchitectureRule> {
+        self.rules.iter().find(|rule| {
+            rule.validate().is_none()
+                && !rule.enforcement().is_false()
+                && rule.forbids(referencing_pack_name, defining_pack_name)
+        })
+    }
+}
+
+impl ValidatorInterface for Checker {
+    fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        let mut error_messages: Vec<String> = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule.validate())
+            .collect();
+
+        for pack_dependency in enforced_pack_dependencies(
+            configuration,
+            ViolationType::ArchitectureRule,
+        ) {
+            let (from_pack, to_pack) =
+                (pack_dependency.from_pack, pack_dependency.to_pack);
+            if let Some(rule) =
+                self.forbidding_rule(&from_pack.name, &to_pack.name)
+            {
+                error_messages.push(format!(
+                    "Invalid 'dependencies' option in '{}'. `{}` cannot depend on `{}` because {}",
+                    from_pack.relative_yml().to_string_lossy(),
+                    from_pack.name,
+                    to_pack.name,
+                    rule,
+                ));
+            }
+        }
+
+        if error_messages.is_empty() {
+            None
+        } else {
+            Some(error_messages)
+        }
+    }
+}
+
+impl CheckerInterface for Checker {
+    fn check(
+        &self,
+        reference: &Reference,
+        configuration: &Configuration,
+        _sigils: &HashMap<std::path::PathBuf, Vec<crate::packs::Sigil>>,
+    ) -> anyhow::Result<Option<Violation>> {
+        let pack_checker =
+            PackChecker::new(configuration, reference, &self.violation_type())?;
+        if !pack_checker.checkable()? {
+            return Ok(None);
+        }
+        let defining_pack = pack_checker.defining_pack.unwrap();
+        let referencing_pack = pack_checker.referencing_pack;
+        let Some(rule) =
+            self.forbidding_rule(&referencing_pack.name, &defining_pack.name)
+        else {
+            return Ok(None);
+        };
+
+        let loc = print_reference_location(reference);
+
+        let message = format!(
+            "{}Architecture rule violation: `{}` belongs to `{}`, which cannot be referenced from `{}` because {}",
+            loc,
+            reference.constant_name,
+            defining_pack.name,
+            referencing_pack.name,
+            rule,
+        );
+
+        Ok(Some(Violation {
+            message,
+            identifier: pack_checker
+                .violation_identifier_for_setting(rule.enforcement()),
+            source_location: reference.source_location.clone(),
+        }))
+    }
+
+    fn violation_type(&self) -> String {
+        "architecture_rule".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn rule(yml: &str) -> ArchitectureRule {
+        serde_yaml::from_str(yml).unwrap()
+    }
+
+    #[test]
+    fn test_may_not_depend_on() {
+        let rule = rule("packs: packs/domain/**\nmay_not_depend_on: packs/web");
+        assert!(rule.forbids("packs/domain/orders", "packs/web"));
+        assert!(rule.forbids("packs/domain/orders", "packs/web/admin"));
+        assert!(!rule.forbids("packs/web/admin", "packs/domain/orders"));
+        assert!(!rule.forbids("packs/domain", "packs/web/admin"));
+        assert_eq!(
+            rule.to_string(),
+            "`packs/domain/**` may not depend on `packs/web`"
+        );
+    }
+
+    #[test]
+    fn test_may_only_be_referenced_by() {
+        let rule = rule(
+            "packs: [packs/experiments/**]\nmay_only_be_referenced_by: [packs/labs, packs/admin]",
+        );
+        assert!(rule.forbids("packs/domain", "packs/experiments/beta"));
+        assert!(!rule.forbids("packs/labs/ui", "packs/experiments/beta"));
+        assert!(
+            !rule.forbids("packs/experiments/alpha", "packs/experiments/beta")
+        );
+        assert!(!rule.forbids("packs/domain", "packs/labs"));
+    }
+
+    #[test]
+    fn test_enforcement() {
+        assert_eq!(
+            rule("packs: packs/domain/**\nmay_not_depend_on: packs/web")
+                .enforcement(),
+            &CheckerSetting::True
+        );
+        assert_eq!(
+            rule("packs: packs/domain/**\nmay_not_depend_on: packs/web\nenforcement: warn")
+                .enforcement(),
+            &CheckerSetting::Warn
+        );
+    }
+
+    #[test]
+    fn test_validate_rule() {
+        assert_eq!(
+            rule("packs: packs/domain/**\nmay_not_depend_on: packs/web")
+                .validate(),
+            None
+        );
+        assert_eq!(
+            rule("packs: packs/domain/**").validate(),
+            Some(String::from("Invalid architecture rule for `packs/domain/**`. A rule needs `packs` and exactly one of `may_not_depend_on` or `may_only_be_referenced_by`"))
+        );
+    }
+}
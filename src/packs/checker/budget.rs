@@ -0,0 +1,260 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::packs::pack::{Pack, ViolationBudget};
+use crate::packs::{Configuration, PackageTodo};
+
+// A pack that records more violations in its package_todo.yml than its
+// `violation_budget` allows.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct BudgetOverrun {
+    pub pack_name: String,
+    // `None` when the budget is a total across all violation types
+    pub violation_type: Option<String>,
+    pub recorded: usize,
+    pub budget: usize,
+}
+
+impl fmt::Display for BudgetOverrun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.violation_type {
+            Some(violation_type) => write!(
+                f,
+                "{} has {} recorded {} violation(s), which exceeds its {} violation_budget of {}",
+                self.pack_name,
+                self.recorded,
+                violation_type,
+                violation_type,
+                self.budget,
+            ),
+            None => write!(
+                f,
+                "{} has {} recorded violation(s), which exceeds its violation_budget of {}",
+                self.pack_name, self.recorded, self.budget,
+            ),
+        }
+    }
+}
+
+fn recorded_counts_by_type(
+    pack_name: &str,
+    package_todo: &PackageTodo,
+) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for identifier in package_todo.violation_identifiers(pack_name) {
+        *counts.entry(identifier.violation_type).or_default() += 1;
+    }
+    counts
+}
+
+pub(crate) fn overruns(pack: &Pack) -> Vec<BudgetOverrun> {
+    let Some(budget) = &pack.violation_budget else {
+        return vec![];
+    };
+    let counts = recorded_counts_by_type(&pack.name, &pack.package_todo);
+
+    match budget {
+        ViolationBudget::Total(budget) => {
+            let recorded = counts.values().sum();
+            if recorded > *budget {
+                vec![BudgetOverrun {
+                    pack_name: pack.name.clone(),
+                    violation_type: None,
+                    recorded,
+                    budget: *budget,
+                }]
+            } else {
+                vec![]
+            }
+        }
+        ViolationBudget::ByViolationType(budgets) => budgets
+            .iter()
+            .filter_map(|(violation_type, budget)| {
+                let recorded =
+                    counts.get(violation_type).copied().unwrap_or_default();
+                (recorded > *budget).then(|| BudgetOverrun {
+                    pack_name: pack.name.clone(),
+                    violation_type: Some(violation_type.clone()),
+                    recorded,
+                    budget: *budget,
+                })
+            })
+            .collect(),
+    }
+}
+
+// Budgets only ever go down: each budget is lowered to the recorded count when fewer
+// violations are recorded, and is left alone otherwise.
+fn lowered_budget(
+    budget: &ViolationBudget,
+    counts: &BTreeMap<String, usize>,
+) -> ViolationBudget {
+    match budget {
+        ViolationBudget::Total(budget) => {
+            ViolationBudget::Total((*budget).min(counts.values().sum()))
+        }
+        ViolationBudget::ByViolationType(budgets) => {
+            ViolationBudget::ByViolationType(
+                budgets
+                    .iter()
+                    .map(|(violation_type, budget)| {
+                        let recorded = counts
+                            .get(violation_type)
+                            .copied()
+                            .unwrap_or_default();
+                        (violation_type.clone(), (*budget).min(recorded))
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
+
+// Lowers the `violation_budget` of each responsible pack to match its updated package_todo.yml.
+// Packs with a lowered budget are added to `updated_packs` for the caller to write.
+// Returns their names.
+pub(crate) fn lower_budgets(
+    configuration: &Configuration,
+    package_todos_by_pack_name: &HashMap<String, PackageTodo>,
+    responsible_pack_names: &HashSet<String>,
+    updated_packs: &mut BTreeMap<String, Pack>,
+) -> Vec<String> {
+    let empty_package_todo = PackageTodo::default();
+    let mut lowered_pack_names = vec![];
+
+    for pack in &configuration.pack_set.packs {
+        let Some(budget) = &pack.violation_budget else {
+            continue;
+        };
+        if !responsible_pack_names.contains(&pack.name) {
+            continue;
+        }
+
+        let package_todo = package_todos_by_pack_name
+            .get(&pack.name)
+            .unwrap_or(&empty_package_todo);
+        let counts = recorded_counts_by_type(&pack.name, package_todo);
+        let lowered = lowered_budget(budget, &counts);
+        if &lowered != budget {
+            updated_packs
+                .entry(pack.name.clone())
+                .or_insert_with(|| pack.clone())
+                .violation_budget = Some(lowered);
+            lowered_pack_names.push(pack.name.clone());
+        }
+    }
+
+    lowered_pack_names.sort();
+    lowered_pack_names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::package_todo::ViolationGroup;
+    use pretty_assertions::assert_eq;
+
+    fn pack_with_budget(budget: ViolationBudget) -> Pack {
+        let group = ViolationGroup {
+            violation_types: ["dependency", "privacy"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            files: ["packs/foo/app/a.rb", "packs/foo/app/b.rb"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            ..ViolationGroup::default()
+        };
+        let package_todo = PackageTodo {
+            violations_by_defining_pack: [(
+                String::from("packs/bar"),
+                [(String::from("::Bar"), group)].into(),
+            )]
+            .into(),
+        };
+        Pack {
+            name: String::from("packs/foo"),
+            package_todo,
+            violation_budget: Some(budget),
+            ..Pack::default()
+        }
+    }
+
+    #[test]
+    fn test_total_budget_overrun() {
+        let pack = pack_with_budget(ViolationBudget::Total(3));
+        let overruns = overruns(&pack);
+        assert_eq!(
+            overruns,
+            vec![BudgetOverrun {
+                pack_name: String::from("packs/foo"),
+                violation_type: None,
+                recorded: 4,
+                budget: 3,
+            }]
+        );
+        assert_eq!(
+            overruns[0].to_string(),
+            "packs/foo has 4 recorded violation(s), which exceeds its violation_budget of 3"
+        );
+    }
+
+    #[test]
+    fn test_budget_by_violation_type() {
+        let pack = pack_with_budget(ViolationBudget::ByViolationType(
+            [
+                (String::from("dependency"), 2),
+                (String::from("privacy"), 1),
+            ]
+            .into(),
+        ));
+        let overruns = overruns(&pack);
+        assert_eq!(overruns.len(), 1);
+        assert_eq!(
+            overruns[0].to_string(),
+            "packs/foo has 2 recorded privacy violation(s), which exceeds its privacy violation_budget of 1"
+        );
+    }
+
+    #[test]
+    fn test_lowered_budget_never_increases() {
+        let counts: BTreeMap<String, usize> = [
+            (String::from("dependency"), 1),
+            (String::from("privacy"), 5),
+        ]
+        .into();
+
+        assert_eq!(
+            lowered_budget(&ViolationBudget::Total(10), &counts),
+            ViolationBudget::Total(6)
+        );
+        assert_eq!(
+            lowered_budget(&ViolationBudget::Total(4), &counts),
+            ViolationBudget::Total(4)
+        );
+        assert_eq!(
+            lowered_budget(
+                &ViolationBudget::ByViolationType(
+                    [
+                        (String::from("dependency"), 3),
+                        (String::from("privacy"), 2),
+                        (String::from("layer"), 1),
+                    ]
+                    .into()
+                ),
+                &counts
+            ),
+            ViolationBudget::ByViolationType(
+                [
+                    (String::from("dependency"), 1),
+                    (String::from("privacy"), 2),
+                    (String::from("layer"), 0),
+                ]
+                .into()
+            )
+        );
+    }
+}
@@ -52,11 +52,13 @@ pub mod tests {
             identifier: ViolationIdentifier {
                 violation_type,
                 strict,
+                warning: false,
                 file: String::from("packs/foo/app/services/foo.rb"),
                 constant_name,
                 referencing_pack_name: String::from("packs/foo"),
                 defining_pack_name: String::from("packs/bar"),
             },
+            source_location: SourceLocation { line: 3, column: 1 },
         }
     }
 
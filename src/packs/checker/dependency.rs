@@ -138,10 +138,11 @@ impl CheckerInterface for Checker {
                 defining_pack.name
             ))?;
 
-        if pack_checker
-            .referencing_pack
-            .is_ignored(relative_defining_file, &self.violation_type())?
-        {
+        if pack_checker.referencing_pack.is_ignored(
+            relative_defining_file,
+            &self.violation_type(),
+            configuration.today,
+        )? {
             return Ok(None);
         }
 
@@ -168,6 +169,7 @@ impl CheckerInterface for Checker {
         Ok(Some(Violation {
             message,
             identifier: pack_checker.violation_identifier(),
+            source_location: reference.source_location.clone(),
         }))
     }
 
@@ -301,6 +303,7 @@ mod tests {
                         .map(|s| s.to_string())
                         .collect(),
                     reason: "deprecated".to_string(),
+                    expires_on: None,
                 }]),
                 ..default_referencing_pack()
             },
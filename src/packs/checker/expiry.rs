@@ -0,0 +1,233 @@
+use crate::packs::configuration::TodoStorage;
+use crate::packs::date::Date;
+use crate::packs::pack::{EnforcementGlobsIgnore, Pack};
+use crate::packs::package_todo::{
+    package_todo_path, CENTRAL_PACKAGE_TODO_FILE_NAME,
+};
+use crate::packs::Configuration;
+
+// Notices about todo deadlines and enforcement_globs_ignore entries that have expired
+// or will expire within `expiry_warning_days`. These never fail `check` themselves:
+// once expired, the affected violations are reported like any other violation.
+pub(crate) fn expiry_warnings(configuration: &Configuration) -> Vec<String> {
+    let mut warnings: Vec<String> = configuration
+        .pack_set
+        .packs
+        .iter()
+        .flat_map(|pack| pack_expiry_warnings(configuration, pack))
+        .collect();
+    warnings.sort();
+    warnings
+}
+
+fn pack_expiry_warnings(
+    configuration: &Configuration,
+    pack: &Pack,
+) -> Vec<String> {
+    let today = configuration.today;
+    let warning_days = i64::from(configuration.expiry_warning_days);
+    let mut warnings = vec![];
+
+    if let Some(deadline) = pack.todo_deadline {
+        let recorded_todo = match configuration.todo_storage {
+            TodoStorage::PerPack => package_todo_path(&pack.name),
+            TodoStorage::Central => format!(
+                "The {} entry in {}",
+                pack.name, CENTRAL_PACKAGE_TODO_FILE_NAME
+            ),
+        };
+        let has_recorded_violations =
+            !pack.package_todo.violations_by_defining_pack.is_empty();
+        let days_left = today.days_until(deadline);
+        if !has_recorded_violations {
+            // Nothing left to expire
+        } else if days_left < 0 {
+            warnings.push(format!(
+                "{} expired on {}, so its recorded violations are reported as new violations",
+                recorded_todo,
+                deadline,
+            ));
+        } else if days_left <= warning_days {
+            warnings.push(format!(
+                "{} expires on {} (in {} day(s)). After that, its recorded violations will be reported as new violations",
+                recorded_todo,
+                deadline,
+                days_left,
+            ));
+        }
+    }
+
+    for ignore in pack.enforcement_globs_ignore.iter().flatten() {
+        if let Some(warning) =
+            ignore_expiry_warning(pack, ignore, today, warning_days)
+        {
+            warnings.push(warning);
+        }
+    }
+
+    warnings
+}
+
+fn ignore_expiry_warning(
+    pack: &Pack,
+    ignore: &EnforcementGlobsIgnore,
+    today: Date,
+    warning_days: i64,
+) -> Option<String> {
+    let expires_on = ignore.expires_on?;
+    let days_left = today.days_until(expires_on);
+
+    let mut enforcements: Vec<&String> = ignore.enforcements.iter().collect();
+    enforcements.sort();
+    let enforcements = enforcements
+        .iter()
+        .map(|e| e.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+    let description = format!(
+        "The enforcement_globs_ignore entry for {} in {}",
+        enforcements,
+        pack.relative_yml().display(),
+    );
+    let reason = if ignore.reason.is_empty() {
+        String::new()
+    } else {
+        format!(" (reason: {})", ignore.reason)
+    };
+
+    if days_left < 0 {
+        Some(format!(
+            "{}{} expired on {} and no longer applies",
+            description, reason, expires_on
+        ))
+    } else if days_left <= warning_days {
+        Some(format!(
+            "{}{} expires on {} (in {} day(s))",
+            description, reason, expires_on, days_left
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::packs::configuration::TodoStorage;
+    use crate::packs::package_todo::ViolationGroup;
+    use crate::packs::PackageTodo;
+    use pretty_assertions::assert_eq;
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    fn pack() -> Pack {
+        let package_todo = PackageTodo {
+            violations_by_defining_pack: [(
+                String::from("packs/bar"),
+                [(String::from("::Bar"), ViolationGroup::default())].into(),
+            )]
+            .into(),
+        };
+        Pack {
+            name: String::from("packs/foo"),
+            relative_path: PathBuf::from("packs/foo"),
+            package_todo,
+            ..Pack::default()
+        }
+    }
+
+    fn configuration(today: &str, packs: Vec<Pack>) -> Configuration {
+        let mut configuration = Configuration {
+            today: date(today),
+            expiry_warning_days: 7,
+            ..Configuration::default()
+        };
+        configuration.pack_set.packs = packs;
+        configuration
+    }
+
+    #[test]
+    fn test_todo_deadline_warnings() {
+        let pack = Pack {
+            todo_deadline: Some(date("2024-03-10")),
+            ..pack()
+        };
+
+        assert_eq!(
+            expiry_warnings(&configuration("2024-03-01", vec![pack.clone()])),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            expiry_warnings(&configuration("2024-03-05", vec![pack.clone()])),
+            vec!["packs/foo/package_todo.yml expires on 2024-03-10 (in 5 day(s)). After that, its recorded violations will be reported as new violations"]
+        );
+        assert_eq!(
+            expiry_warnings(&configuration("2024-03-10", vec![pack.clone()])),
+            vec!["packs/foo/package_todo.yml expires on 2024-03-10 (in 0 day(s)). After that, its recorded violations will be reported as new violations"]
+        );
+        assert_eq!(
+            expiry_warnings(&configuration("2024-03-11", vec![pack])),
+            vec!["packs/foo/package_todo.yml expired on 2024-03-10, so its recorded violations are reported as new violations"]
+        );
+    }
+
+    #[test]
+    fn test_todo_deadline_warnings_with_central_todo_storage() {
+        let pack = Pack {
+            todo_deadline: Some(date("2024-03-10")),
+            ..pack()
+        };
+        let configuration = Configuration {
+            todo_storage: TodoStorage::Central,
+            ..configuration("2024-03-11", vec![pack])
+        };
+
+        assert_eq!(
+            expiry_warnings(&configuration),
+            vec!["The packs/foo entry in packs_todo.yml expired on 2024-03-10, so its recorded violations are reported as new violations"]
+        );
+    }
+
+    #[test]
+    fn test_todo_deadline_without_recorded_violations() {
+        let pack = Pack {
+            todo_deadline: Some(date("2024-03-10")),
+            package_todo: PackageTodo::default(),
+            ..pack()
+        };
+        assert_eq!(
+            expiry_warnings(&configuration("2024-03-11", vec![pack])),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_enforcement_globs_ignore_warnings() {
+        let pack = Pack {
+            enforcement_globs_ignore: Some(vec![EnforcementGlobsIgnore {
+                enforcements: ["privacy", "dependency"]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+                ignores: HashSet::new(),
+                reason: "legacy code".to_string(),
+                expires_on: Some(date("2024-03-10")),
+            }]),
+            ..pack()
+        };
+
+        assert_eq!(
+            expiry_warnings(&configuration("2024-03-04", vec![pack.clone()])),
+            vec!["The enforcement_globs_ignore entry for dependency, privacy in packs/foo/package.yml (reason: legacy code) expires on 2024-03-10 (in 6 day(s))"]
+        );
+        assert_eq!(
+            expiry_warnings(&configuration("2024-04-01", vec![pack])),
+            vec!["The enforcement_globs_ignore entry for dependency, privacy in packs/foo/package.yml (reason: legacy code) expired on 2024-03-10 and no longer applies"]
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use super::output_helper::print_reference_location;
-use super::pack_checker::PackChecker;
-use super::CheckerInterface;
+use super::pack_checker::{
+    enforced_pack_dependencies, PackChecker, ViolationType,
+};
+use super::{CheckerInterface, ValidatorInterface};
 use crate::packs::checker::reference::Reference;
-use crate::packs::pack::Pack;
+use crate::packs::pack::{FolderPrivacyPolicy, FolderPrivacyPolicyName, Pack};
 use crate::packs::{Configuration, Violation};
 
 pub struct Checker {}
@@ -27,16 +30,18 @@ impl CheckerInterface for Checker {
             let loc = print_reference_location(reference);
 
             let message = format!(
-                "{}Folder Privacy violation: `{}` belongs to `{}`, which is private to `{}` as it is not a sibling pack or parent pack.",
+                "{}Folder Privacy violation: `{}` belongs to `{}`, which is private to `{}` as it is not {}.",
                 loc,
                 reference.constant_name,
                 defining_pack.name,
                 pack_checker.referencing_pack.name,
+                policy_description(defining_pack),
             );
 
             Ok(Some(Violation {
                 message,
                 identifier: pack_checker.violation_identifier(),
+                source_location: reference.source_location.clone(),
             }))
         } else {
             Ok(None)
@@ -48,6 +53,80 @@ impl CheckerInterface for Checker {
     }
 }
 
+impl ValidatorInterface for Checker {
+    fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        let error_messages: Vec<String> = enforced_pack_dependencies(
+            configuration,
+            ViolationType::FolderPrivacy,
+        )
+        .into_iter()
+        .filter(|pack_dependency| {
+            !folder_visible(pack_dependency.from_pack, pack_dependency.to_pack)
+        })
+        .map(|pack_dependency| {
+            format!(
+                "Invalid 'dependencies' option in '{}'. `{}` is private to its folder, and `{}` is not {}",
+                pack_dependency.from_pack.relative_yml().to_string_lossy(),
+                pack_dependency.to_pack.name,
+                pack_dependency.from_pack.name,
+                policy_description(pack_dependency.to_pack),
+            )
+        })
+        .collect();
+
+        if error_messages.is_empty() {
+            None
+        } else {
+            Some(error_messages)
+        }
+    }
+}
+
+fn policy_description(defining_pack: &Pack) -> String {
+    match defining_pack.folder_privacy_policy() {
+        FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::Default) => {
+            "a sibling pack or parent pack".to_owned()
+        }
+        FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::Siblings) => {
+            "a sibling pack".to_owned()
+        }
+        FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::Parent) => {
+            "the parent pack".to_owned()
+        }
+        FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::SameDomain) => {
+            format!(
+                "in the domain folder `{}`",
+                domain_folder(&defining_pack.relative_path).display()
+            )
+        }
+        FolderPrivacyPolicy::AncestorDepths { ancestor_depths } => {
+            format!(
+                "an ancestor pack at depth {}",
+                ancestor_depths
+                    .iter()
+                    .map(|depth| depth.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            )
+        }
+    }
+}
+
+// The first two folders of a pack's path, e.g. `packs/payments` for
+// `packs/payments/refunds`
+fn domain_folder(relative_path: &Path) -> PathBuf {
+    relative_path.components().take(2).collect()
+}
+
+// The number of folders `ancestor` is above `descendant`, if it contains it
+fn ancestor_depth(ancestor: &Path, descendant: &Path) -> Option<usize> {
+    let nested = descendant.strip_prefix(ancestor).ok()?;
+    match nested.components().count() {
+        0 => None,
+        depth => Some(depth),
+    }
+}
+
 fn folder_visible(referencing_pack: &Pack, defining_pack: &Pack) -> bool {
     if defining_pack.enforce_folder_privacy().is_false() {
         return true;
@@ -57,19 +136,32 @@ fn folder_visible(referencing_pack: &Pack, defining_pack: &Pack) -> bool {
         return true; // root pack is visible to all
     }
 
-    if let (Some(from_pack_parent_path), Some(to_pack_parent_path)) = (
-        referencing_pack.relative_path.parent(),
-        defining_pack.relative_path.parent(),
-    ) {
-        if from_pack_parent_path == to_pack_parent_path {
-            return true; // siblings are visible to each other
+    let referencing_path = referencing_pack.relative_path.as_path();
+    let defining_path = defining_pack.relative_path.as_path();
+    let is_sibling = referencing_path.parent() == defining_path.parent();
+
+    match defining_pack.folder_privacy_policy() {
+        FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::Default) => {
+            // siblings are visible to each other
+            is_sibling
+                || defining_path
+                    .to_string_lossy()
+                    .starts_with(referencing_path.to_string_lossy().as_ref())
+        }
+        FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::Siblings) => {
+            is_sibling
+        }
+        FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::Parent) => {
+            ancestor_depth(referencing_path, defining_path) == Some(1)
+        }
+        FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::SameDomain) => {
+            domain_folder(referencing_path) == domain_folder(defining_path)
+        }
+        FolderPrivacyPolicy::AncestorDepths { ancestor_depths } => {
+            ancestor_depth(referencing_path, defining_path)
+                .is_some_and(|depth| ancestor_depths.contains(&depth))
         }
     }
-
-    defining_pack
-        .relative_path
-        .to_string_lossy()
-        .starts_with(referencing_pack.relative_path.to_string_lossy().as_ref())
 }
 
 #[cfg(test)]
@@ -80,7 +172,7 @@ mod tests {
             build_expected_violation, default_defining_pack,
             default_referencing_pack, test_check, TestChecker,
         },
-        pack::{CheckerSetting, EnforcementGlobsIgnore},
+        pack::{CheckerSetting, EnforcementGlobsIgnore, FolderPrivacySetting},
     };
     use std::path::PathBuf;
 
@@ -92,7 +184,7 @@ mod tests {
             referenced_constant_name: Some(String::from("::Bar")),
             defining_pack: Some(Pack {
                 name: "packs/bar".to_owned(),
-                enforce_folder_privacy: Some(CheckerSetting::True),
+                enforce_folder_privacy: Some(CheckerSetting::True.into()),
                 ..default_defining_pack()
             }),
             referencing_pack: Pack{
@@ -113,7 +205,7 @@ mod tests {
             referenced_constant_name: Some(String::from("::Bar")),
             defining_pack: Some(Pack {
                 name: "packs/bar".to_owned(),
-                enforce_folder_privacy: Some(CheckerSetting::True),
+                enforce_folder_privacy: Some(CheckerSetting::True.into()),
                 enforcement_globs_ignore: Some(vec![EnforcementGlobsIgnore {
                     enforcements: ["folder_privacy"]
                         .iter()
@@ -124,6 +216,7 @@ mod tests {
                         .map(|s| s.to_string())
                         .collect(),
                     reason: "deprecated".to_string(),
+                    expires_on: None,
                 }]),
                 ..default_defining_pack()
             }),
@@ -143,7 +236,7 @@ mod tests {
             referenced_constant_name: Some(String::from("::Bar")),
             defining_pack: Some(Pack {
                 name: "packs/bar".to_owned(),
-                enforce_folder_privacy: Some(CheckerSetting::Strict),
+                enforce_folder_privacy: Some(CheckerSetting::Strict.into()),
                 ..default_defining_pack()
             }),
             referencing_pack: Pack{
@@ -164,7 +257,7 @@ mod tests {
             referenced_constant_name: Some(String::from("::Bar")),
             defining_pack: Some(Pack {
                 name: "packs/bar".to_owned(),
-                enforce_folder_privacy: Some(CheckerSetting::False),
+                enforce_folder_privacy: Some(CheckerSetting::False.into()),
                 ..default_defining_pack()
             }),
             referencing_pack: Pack {
@@ -194,7 +287,8 @@ mod tests {
         let to_pack = Pack {
             name: to_pack_path.to_string(),
             relative_path: PathBuf::from(&to_pack_path),
-            enforce_folder_privacy: to_pack_enforce_folder_privacy,
+            enforce_folder_privacy: to_pack_enforce_folder_privacy
+                .map(FolderPrivacySetting::from),
             ..Pack::default()
         };
 
@@ -280,4 +374,83 @@ mod tests {
             true,
         );
     }
+
+    fn assert_folder_privacy_with_policy(
+        policy: FolderPrivacyPolicy,
+        cases: &[(&str, &str, bool)],
+    ) {
+        for (from_pack_path, to_pack_path, expected) in cases {
+            let from_pack = Pack {
+                name: from_pack_path.to_string(),
+                relative_path: PathBuf::from(from_pack_path),
+                ..Pack::default()
+            };
+            let to_pack = Pack {
+                name: to_pack_path.to_string(),
+                relative_path: PathBuf::from(to_pack_path),
+                enforce_folder_privacy: Some(FolderPrivacySetting {
+                    setting: CheckerSetting::True,
+                    policy: policy.clone(),
+                }),
+                ..Pack::default()
+            };
+            assert_eq!(
+                *expected,
+                folder_visible(&from_pack, &to_pack),
+                "{} -> {}",
+                from_pack_path,
+                to_pack_path
+            );
+        }
+    }
+
+    #[test]
+    fn test_folder_privacy_siblings_policy() {
+        assert_folder_privacy_with_policy(
+            FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::Siblings),
+            &[
+                ("packs/foo/baz", "packs/foo/bar", true),
+                ("packs/foo", "packs/foo/bar", false),
+                (".", "packs/foo/bar", true),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_folder_privacy_parent_policy() {
+        assert_folder_privacy_with_policy(
+            FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::Parent),
+            &[
+                ("packs/foo", "packs/foo/bar", true),
+                ("packs", "packs/foo/bar", false),
+                ("packs/foo/baz", "packs/foo/bar", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_folder_privacy_same_domain_policy() {
+        assert_folder_privacy_with_policy(
+            FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::SameDomain),
+            &[
+                ("packs/payments/checkout", "packs/payments/refunds", true),
+                ("packs/payments", "packs/payments/refunds/api", true),
+                ("packs/billing/invoices", "packs/payments/refunds", false),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_folder_privacy_ancestor_depths_policy() {
+        assert_folder_privacy_with_policy(
+            FolderPrivacyPolicy::AncestorDepths {
+                ancestor_depths: vec![2],
+            },
+            &[
+                ("packs/foo", "packs/foo/bar/baz", true),
+                ("packs/foo/bar", "packs/foo/bar/baz", false),
+                ("packs/foo/bar/qux", "packs/foo/bar/baz", false),
+            ],
+        );
+    }
 }
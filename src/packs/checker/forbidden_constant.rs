@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::output_helper::print_reference_location;
+use super::pack_checker::is_strict_violation;
+use super::privacy::constant_or_namespace_matches;
+use super::{CheckerInterface, ValidatorInterface, ViolationIdentifier};
+use crate::packs::checker::Reference;
+use crate::packs::ignored;
+use crate::packs::pack::{
+    deserialize_checker_setting, serialize_checker_setting, CheckerSetting,
+};
+use crate::packs::raw_configuration::string_or_vec;
+use crate::packs::{Configuration, Violation};
+
+// Constants that may not be referenced, declared in `forbidden_constants`. Entries can
+// use the same patterns as private_constants, and also forbid the constants nested
+// within them.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct ForbiddenConstant {
+    #[serde(deserialize_with = "string_or_vec")]
+    pub constants: Vec<String>,
+
+    // What to use instead, shown with each violation
+    pub replacement: String,
+
+    // The referencing packs the constants are forbidden in, all packs when empty
+    #[serde(
+        default,
+        deserialize_with = "string_or_vec",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub packs: Vec<String>,
+
+    // How violations are enforced, e.g. `warn` or `strict`. Defaults to `true`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_checker_setting",
+        deserialize_with = "deserialize_checker_setting"
+    )]
+    pub enforcement: Option<CheckerSetting>,
+}
+
+impl ForbiddenConstant {
+    fn enforcement(&self) -> &CheckerSetting {
+        self.enforcement.as_ref().unwrap_or(&CheckerSetting::True)
+    }
+
+    fn forbids(
+        &self,
+        constant_name: &str,
+        referencing_pack_name: &str,
+    ) -> bool {
+        // Names the resolver can't find are matched as written, e.g. `Net::HTTP`
+        let constant_name =
+            format!("::{}", constant_name.trim_start_matches("::"));
+        self.constants.iter().any(|pattern| {
+            constant_or_namespace_matches(pattern, &constant_name)
+        }) && (self.packs.is_empty()
+            || self.packs.iter().any(|pattern| {
+                ignored::is_match_or_within(pattern, referencing_pack_name)
+            }))
+    }
+}
+
+pub struct Checker {
+    pub forbidden_constants: Vec<ForbiddenConstant>,
+}
+
+impl CheckerInterface for Checker {
+    fn check(
+        &self,
+        reference: &Reference,
+        configuration: &Configuration,
+        _sigils: &HashMap<std::path::PathBuf, Vec<crate::packs::Sigil>>,
+    ) -> anyhow::Result<Option<Violation>> {
+        // A pack can use its own constants
+        if reference.defining_pack_name.as_ref()
+            == Some(&reference.referencing_pack_name)
+        {
+            return Ok(None);
+        }
+        let Some(forbidden_constant) =
+            self.forbidden_constants.iter().find(|forbidden_constant| {
+                !forbidden_constant.enforcement().is_false()
+                    && forbidden_constant.forbids(
+                        &reference.constant_name,
+                        &reference.referencing_pack_name,
+                    )
+            })
+        else {
+            return Ok(None);
+        };
+
+        let referencing_pack =
+            reference.referencing_pack(&configuration.pack_set)?;
+        if referencing_pack.is_ignored(
+            &reference.relative_referencing_file,
+            &self.violation_type(),
+            configuration.today,
+        )? {
+            return Ok(None);
+        }
+
+        let loc = print_reference_location(reference);
+
+        let message = format!(
+            "{}Forbidden constant violation: `{}` cannot be referenced from `{}`. {}",
+            loc,
+            reference.constant_name,
+            reference.referencing_pack_name,
+            forbidden_constant.replacement,
+        );
+
+        let setting = forbidden_constant.enforcement();
+        let mut identifier = ViolationIdentifier {
+            violation_type: self.violation_type(),
+            strict: false,
+            warning: setting.is_warn(),
+            file: reference.relative_referencing_file.clone(),
+            constant_name: reference.constant_name.clone(),
+            referencing_pack_name: reference.referencing_pack_name.clone(),
+            // Constants that aren't defined in a pack, such as those from gems, are
+            // recorded under the root pack
+            defining_pack_name: reference
+                .defining_pack_name
+                .clone()
+                .unwrap_or_else(|| String::from(".")),
+        };
+        identifier.strict =
+            is_strict_violation(configuration, setting, &identifier);
+
+        Ok(Some(Violation {
+            message,
+            identifier,
+            source_location: reference.source_location.clone(),
+        }))
+    }
+
+    fn violation_type(&self) -> String {
+        "forbidden_constant".to_owned()
+    }
+}
+
+// `packs` patterns must each match a pack, so that entries don't silently stop
+// applying when packs are renamed or moved
+impl ValidatorInterface for Checker {
+    fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        let error_messages: Vec<String> = self
+            .forbidden_constants
+            .iter()
+            .flat_map(|forbidden_constant| {
+                forbidden_constant
+                    .packs
+                    .iter()
+                    .filter(|pattern| {
+                        !configuration.pack_set.packs.iter().any(|pack| {
+                            ignored::is_match_or_within(pattern, &pack.name)
+                        })
+                    })
+                    .map(|pattern| {
+                        format!(
+                            "Invalid 'packs' option in forbidden_constants for {}. `{}` does not match any pack",
+                            forbidden_constant
+                                .constants
+                                .iter()
+                                .map(|constant| format!("`{}`", constant))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                            pattern,
+                        )
+                    })
+            })
+            .collect();
+
+        if error_messages.is_empty() {
+            None
+        } else {
+            Some(error_messages)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forbidden_constant(yml: &str) -> ForbiddenConstant {
+        serde_yaml::from_str(yml).unwrap()
+    }
+
+    #[test]
+    fn test_forbids_resolved_and_unresolved_names() {
+        let forbidden_constant = forbidden_constant(
+            "constants: \"::Net::HTTP\"\nreplacement: Use ::HttpClient",
+        );
+        assert!(forbidden_constant.forbids("::Net::HTTP", "packs/foo"));
+        assert!(forbidden_constant.forbids("Net::HTTP", "packs/foo"));
+        assert!(forbidden_constant.forbids("Net::HTTP::Get", "packs/foo"));
+        assert!(!forbidden_constant.forbids("::Net", "packs/foo"));
+        assert!(!forbidden_constant.forbids("::Net::HTTPS", "packs/foo"));
+    }
+
+    #[test]
+    fn test_forbids_in_packs() {
+        let forbidden_constant = forbidden_constant(
+            "constants: [\"::Sidekiq::Client\", \"::Legacy::*\"]\nreplacement: Use jobs\npacks: packs/domain/**",
+        );
+        assert!(forbidden_constant
+            .forbids("::Sidekiq::Client", "packs/domain/orders"));
+        assert!(forbidden_constant
+            .forbids("::Legacy::Billing", "packs/domain/orders"));
+        assert!(!forbidden_constant.forbids("::Sidekiq::Client", "packs/web"));
+    }
+}
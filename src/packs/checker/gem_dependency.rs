@@ -0,0 +1,392 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use tracing::debug;
+
+use super::output_helper::print_reference_location;
+use super::pack_checker::is_strict_violation;
+use super::{CheckerInterface, ValidatorInterface, ViolationIdentifier};
+use crate::packs::caching::CacheResult;
+use crate::packs::checker::Reference;
+use crate::packs::file_utils::glob_ruby_files_in_dirs;
+use crate::packs::parsing::process_from_ruby_path_experimental;
+use crate::packs::{Configuration, ProcessedFile, Violation};
+
+// A gem listed in the specs of a Gemfile.lock, e.g. `    sidekiq (7.1.0)`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LockedGem {
+    pub name: String,
+    pub version: String,
+}
+
+impl LockedGem {
+    // Gems are installed in `<gemdir>/<name>-<version>`. A directory named after the
+    // gem alone is also accepted, e.g. for vendored gems.
+    fn source_dir(&self, gemdir: &Path) -> Option<PathBuf> {
+        [
+            gemdir.join(format!("{}-{}", self.name, self.version)),
+            gemdir.join(&self.name),
+        ]
+        .into_iter()
+        .find(|dir| dir.is_dir())
+    }
+}
+
+pub(crate) fn parse_gemfile_lock(contents: &str) -> Vec<LockedGem> {
+    // Specs are indented by four spaces, and their own dependencies by six
+    contents
+        .lines()
+        .filter_map(|line| line.strip_prefix("    "))
+        .filter(|spec| !spec.starts_with(' '))
+        .filter_map(|spec| {
+            let (name, version) = spec.split_once(" (")?;
+            Some(LockedGem {
+                name: name.to_owned(),
+                version: version.strip_suffix(')')?.to_owned(),
+            })
+        })
+        .collect()
+}
+
+fn gemfile_lock(configuration: &Configuration) -> PathBuf {
+    configuration.absolute_root.join("Gemfile.lock")
+}
+
+fn read_gemfile_lock(
+    configuration: &Configuration,
+) -> anyhow::Result<Vec<LockedGem>> {
+    let gemfile_lock = gemfile_lock(configuration);
+    let contents = std::fs::read_to_string(&gemfile_lock).context(format!(
+        "Failed to read {:?}, which is needed to check gem_dependencies",
+        gemfile_lock
+    ))?;
+    Ok(parse_gemfile_lock(&contents))
+}
+
+// Ruby and gem sources are parsed with the experimental parser whichever parser the
+// application uses, since the constants they define aren't autoloaded. They rarely
+// change, so they are cached like application files.
+fn process_sources(
+    configuration: &Configuration,
+    paths: &[PathBuf],
+) -> anyhow::Result<Vec<ProcessedFile>> {
+    let cache = configuration.get_cache_for_parser(true);
+    paths
+        .par_iter()
+        .map(|path| -> anyhow::Result<ProcessedFile> {
+            let processed_file = match cache.get(path)? {
+                CacheResult::Processed(processed_file) => processed_file,
+                CacheResult::Miss(empty_cache_entry) => {
+                    let processed_file = process_from_ruby_path_experimental(
+                        path,
+                        configuration,
+                    )?;
+                    cache.write(&empty_cache_entry, &processed_file)?;
+                    processed_file
+                }
+            };
+            Ok(processed_file)
+        })
+        .collect()
+}
+
+// Maps each constant defined in the sources of a locked gem to the gems defining it.
+// Constants Ruby itself defines in `rubydir` are left out, since gems commonly reopen
+// them, e.g. to add core extensions, like `pks expose-monkey-patches` reports.
+fn build_gem_constants(
+    configuration: &Configuration,
+    gemdir: &Path,
+    rubydir: &Path,
+) -> anyhow::Result<HashMap<String, BTreeSet<String>>> {
+    debug!("Parsing gem sources in {:?}", gemdir);
+    let mut gem_dirs: Vec<(String, PathBuf)> = vec![];
+    for locked_gem in read_gemfile_lock(configuration)? {
+        match locked_gem.source_dir(gemdir) {
+            Some(dir) => gem_dirs.push((locked_gem.name, dir)),
+            None => debug!(
+                "No sources found for gem {} ({}) in {:?}",
+                locked_gem.name, locked_gem.version, gemdir
+            ),
+        }
+    }
+
+    let (gem_names, gem_files): (Vec<&String>, Vec<PathBuf>) = gem_dirs
+        .iter()
+        .flat_map(|(gem_name, dir)| {
+            glob_ruby_files_in_dirs(vec![dir])
+                .into_iter()
+                .map(move |path| (gem_name, path))
+        })
+        .unzip();
+    let ruby_files = glob_ruby_files_in_dirs(vec![&rubydir.to_path_buf()]);
+
+    let mut gem_constants: HashMap<String, BTreeSet<String>> = HashMap::new();
+    let processed_gem_files = process_sources(configuration, &gem_files)?;
+    for (gem_name, processed_file) in
+        gem_names.into_iter().zip(processed_gem_files)
+    {
+        for definition in processed_file.definitions {
+            gem_constants
+                .entry(definition.fully_qualified_name)
+                .or_default()
+                .insert(gem_name.clone());
+        }
+    }
+    for processed_file in process_sources(configuration, &ruby_files)? {
+        for definition in processed_file.definitions {
+            gem_constants.remove(&definition.fully_qualified_name);
+        }
+    }
+    debug!("Finished parsing gem sources in {:?}", gemdir);
+
+    Ok(gem_constants)
+}
+
+#[derive(Default)]
+pub struct Checker {
+    pub gem_constants: HashMap<String, BTreeSet<String>>,
+}
+
+impl Checker {
+    // Gem sources are only parsed when a pack enforces gem_dependencies
+    pub(crate) fn build(configuration: &Configuration) -> anyhow::Result<Self> {
+        let enforced = configuration.pack_set.packs.iter().any(|pack| {
+            pack.enforce_gem_dependencies
+                .as_ref()
+                .is_some_and(|setting| !setting.is_false())
+        });
+        let gem_constants =
+            match (&configuration.gemdir, &configuration.rubydir) {
+                (Some(gemdir), Some(rubydir)) if enforced => {
+                    build_gem_constants(configuration, gemdir, rubydir)?
+                }
+                _ => HashMap::new(),
+            };
+        Ok(Checker { gem_constants })
+    }
+
+    // The gems defining the constant, or else the closest namespace it's nested in
+    fn defining_gems(&self, constant_name: &str) -> Option<&BTreeSet<String>> {
+        // Names the resolver can't find are matched as written, e.g. `Sidekiq::Client`
+        let mut constant_name =
+            format!("::{}", constant_name.trim_start_matches("::"));
+        loop {
+            if let Some(gems) = self.gem_constants.get(&constant_name) {
+                return Some(gems);
+            }
+            constant_name.truncate(constant_name.rfind("::")?);
+            if constant_name.is_empty() {
+                return None;
+            }
+        }
+    }
+}
+
+impl CheckerInterface for Checker {
+    fn check(
+        &self,
+        reference: &Reference,
+        configuration: &Configuration,
+        _sigils: &HashMap<PathBuf, Vec<crate::packs::Sigil>>,
+    ) -> anyhow::Result<Option<Violation>> {
+        // Constants defined in a pack aren't gem constants, even when a gem defines
+        // them too
+        if reference.defining_pack_name.is_some() {
+            return Ok(None);
+        }
+        let Some(defining_gems) = self.defining_gems(&reference.constant_name)
+        else {
+            return Ok(None);
+        };
+
+        let referencing_pack =
+            reference.referencing_pack(&configuration.pack_set)?;
+        let setting = referencing_pack
+            .enforce_gem_dependencies
+            .clone()
+            .unwrap_or_default();
+        if setting.is_false()
+            || defining_gems
+                .iter()
+                .any(|gem| referencing_pack.gem_dependencies.contains(gem))
+            || referencing_pack.is_ignored(
+                &reference.relative_referencing_file,
+                &self.violation_type(),
+                configuration.today,
+            )?
+        {
+            return Ok(None);
+        }
+
+        let loc = print_reference_location(reference);
+        let gems = defining_gems
+            .iter()
+            .map(|gem| format!("`{}`", gem))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let message = format!(
+            "{}Gem dependency violation: `{}` belongs to gem {}, which is not a gem_dependency of `{}`",
+            loc, reference.constant_name, gems, reference.referencing_pack_name,
+        );
+
+        let mut identifier = ViolationIdentifier {
+            violation_type: self.violation_type(),
+            strict: false,
+            warning: setting.is_warn(),
+            file: reference.relative_referencing_file.clone(),
+            constant_name: reference.constant_name.clone(),
+            referencing_pack_name: reference.referencing_pack_name.clone(),
+            // Gem constants aren't defined in a pack, so they are recorded under the
+            // root pack
+            defining_pack_name: String::from("."),
+        };
+        identifier.strict =
+            is_strict_violation(configuration, &setting, &identifier);
+
+        Ok(Some(Violation {
+            message,
+            identifier,
+            source_location: reference.source_location.clone(),
+        }))
+    }
+
+    fn violation_type(&self) -> String {
+        "gem_dependency".to_owned()
+    }
+}
+
+impl ValidatorInterface for Checker {
+    fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        // gem_dependencies are only checked when `--gemdir` is given
+        configuration.gemdir.as_ref()?;
+
+        let locked_gems = match read_gemfile_lock(configuration) {
+            Ok(locked_gems) => locked_gems,
+            Err(error) => return Some(vec![format!("{:#}", error)]),
+        };
+        let mut error_messages: Vec<String> = vec![];
+        for pack in &configuration.pack_set.packs {
+            let mut gem_dependencies: Vec<&String> =
+                pack.gem_dependencies.iter().collect();
+            gem_dependencies.sort();
+            for gem_dependency in gem_dependencies {
+                if !locked_gems.iter().any(|gem| &gem.name == gem_dependency) {
+                    error_messages.push(format!(
+                        "Invalid 'gem_dependencies' option in '{}'. `{}` is not in Gemfile.lock",
+                        pack.relative_yml().to_string_lossy(),
+                        gem_dependency,
+                    ));
+                }
+            }
+        }
+
+        if error_messages.is_empty() {
+            None
+        } else {
+            Some(error_messages)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::configuration;
+    use crate::packs::pack::CheckerSetting;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_gemfile_lock() {
+        let contents = "GEM
+  remote: https://rubygems.org/
+  specs:
+    connection_pool (2.4.1)
+    nokogiri (1.15.4-x86_64-linux)
+      racc (~> 1.4)
+    sidekiq (7.1.0)
+      connection_pool (>= 2.3.0)
+
+PLATFORMS
+  x86_64-linux
+
+DEPENDENCIES
+  sidekiq
+
+BUNDLED WITH
+   2.4.10
+";
+        let locked_gem = |name: &str, version: &str| LockedGem {
+            name: name.to_owned(),
+            version: version.to_owned(),
+        };
+        assert_eq!(
+            parse_gemfile_lock(contents),
+            vec![
+                locked_gem("connection_pool", "2.4.1"),
+                locked_gem("nokogiri", "1.15.4-x86_64-linux"),
+                locked_gem("sidekiq", "7.1.0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_defining_gems() {
+        let checker = Checker {
+            gem_constants: HashMap::from([
+                (
+                    String::from("::Sidekiq"),
+                    BTreeSet::from([String::from("sidekiq")]),
+                ),
+                (
+                    String::from("::Sidekiq::Web"),
+                    BTreeSet::from([String::from("sidekiq-web")]),
+                ),
+            ]),
+        };
+        let gems = |names: &[&str]| {
+            names.iter().map(|name| name.to_string()).collect()
+        };
+        assert_eq!(checker.defining_gems("Sidekiq"), Some(&gems(&["sidekiq"])));
+        assert_eq!(
+            checker.defining_gems("::Sidekiq::Client"),
+            Some(&gems(&["sidekiq"]))
+        );
+        assert_eq!(
+            checker.defining_gems("Sidekiq::Web::Router"),
+            Some(&gems(&["sidekiq-web"]))
+        );
+        assert_eq!(checker.defining_gems("::Rails"), None);
+    }
+
+    #[test]
+    fn test_build_only_parses_gems_when_enforced() -> anyhow::Result<()> {
+        let absolute_root =
+            PathBuf::from("tests/fixtures/gem_dependencies").canonicalize()?;
+        let mut configuration = configuration::get(&absolute_root, &10)?;
+        configuration.gemdir = Some(absolute_root.join("gemdir_stub"));
+        configuration.rubydir = Some(absolute_root.join("rubydir_stub"));
+        let gem_constants = Checker::build(&configuration)?.gem_constants;
+        assert_eq!(
+            gem_constants.get("::Sidekiq::Client"),
+            Some(&BTreeSet::from([String::from("sidekiq")]))
+        );
+        // Reopened by sidekiq
+        assert_eq!(
+            gem_constants.get("::ConnectionPool"),
+            Some(&BTreeSet::from([
+                String::from("connection_pool"),
+                String::from("sidekiq")
+            ]))
+        );
+        // Reopened by sidekiq's core extensions, but defined by Ruby
+        assert!(!gem_constants.contains_key("::String"));
+
+        for pack in configuration.pack_set.packs.iter_mut() {
+            pack.enforce_gem_dependencies = Some(CheckerSetting::False);
+        }
+        assert!(Checker::build(&configuration)?.gem_constants.is_empty());
+        Ok(())
+    }
+}
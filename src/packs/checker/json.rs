@@ -0,0 +1,271 @@
+use serde::Serialize;
+
+use crate::packs::blame::Blame;
+use crate::packs::package_todo::PackageTodoDiff;
+
+use super::budget::BudgetOverrun;
+use super::suppression::UnusedSuppression;
+use super::{
+    build_strict_violation_message, CheckAllResult, StaleViolation, Violation,
+    ViolationIdentifier,
+};
+
+#[derive(Serialize)]
+struct JsonViolation<'a> {
+    #[serde(flatten)]
+    identifier: &'a ViolationIdentifier,
+    line: usize,
+    column: usize,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blame: Option<&'a Blame>,
+}
+
+#[derive(Serialize)]
+struct AuthorCount<'a> {
+    author: &'a str,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct CheckReport<'a> {
+    violations: Vec<JsonViolation<'a>>,
+    // Violations of `warn` settings, which don't fail `check`
+    warnings: Vec<JsonViolation<'a>>,
+    // Only set when running `check --blame`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    violations_by_author: Option<Vec<AuthorCount<'a>>>,
+    stale_violations: Vec<&'a StaleViolation>,
+    strict_mode_violations: Vec<String>,
+    budget_overruns: &'a [BudgetOverrun],
+    expiry_warnings: &'a [String],
+    unused_suppressions: &'a [UnusedSuppression],
+}
+
+#[derive(Serialize)]
+struct UpdateReport<'a> {
+    recorded_violations: Vec<JsonViolation<'a>>,
+    strict_mode_violations: Vec<String>,
+}
+
+fn json_violations<'a>(
+    violations: impl IntoIterator<Item = &'a Violation>,
+    blame: impl Fn(&Violation) -> Option<&'a Blame>,
+) -> Vec<JsonViolation<'a>> {
+    let mut json_violations: Vec<JsonViolation> = violations
+        .into_iter()
+        .map(|violation| JsonViolation {
+            identifier: &violation.identifier,
+            line: violation.source_location.line,
+            column: violation.source_location.column,
+            // Messages are colored for terminals
+            message: strip_ansi_escapes::strip_str(&violation.message),
+            blame: blame(violation),
+        })
+        .collect();
+    json_violations.sort_by(|a, b| {
+        a.identifier
+            .referencing_pack_name
+            .cmp(&b.identifier.referencing_pack_name)
+            .then_with(|| a.identifier.file.cmp(&b.identifier.file))
+            .then_with(|| a.line.cmp(&b.line))
+            .then_with(|| a.message.cmp(&b.message))
+    });
+    json_violations
+}
+
+fn strict_violation_messages<'a>(
+    strict: impl IntoIterator<Item = &'a ViolationIdentifier>,
+) -> Vec<String> {
+    let mut messages: Vec<String> = strict
+        .into_iter()
+        .map(build_strict_violation_message)
+        .collect();
+    messages.sort();
+    messages.dedup();
+    messages
+}
+
+fn to_json<T: Serialize>(report: &T) -> String {
+    serde_json::to_string_pretty(report)
+        .expect("Reports only contain strings and numbers")
+}
+
+// Renders `check` results as JSON, e.g. for other tools to consume.
+pub(crate) fn check_report(result: &CheckAllResult) -> String {
+    let mut stale_violations: Vec<&StaleViolation> =
+        result.stale_violations.iter().collect();
+    stale_violations.sort_by(|a, b| {
+        let (a, b) = (&a.identifier, &b.identifier);
+        (&a.referencing_pack_name, &a.file, &a.constant_name).cmp(&(
+            &b.referencing_pack_name,
+            &b.file,
+            &b.constant_name,
+        ))
+    });
+
+    let report = CheckReport {
+        violations: json_violations(&result.reportable_violations, |v| {
+            result.blames.get(&v.location_key())
+        }),
+        warnings: json_violations(&result.warning_violations, |_| None),
+        violations_by_author: (!result.blames.is_empty()).then(|| {
+            result
+                .violation_counts_by_author()
+                .into_iter()
+                .map(|(author, count)| AuthorCount { author, count })
+                .collect()
+        }),
+        stale_violations,
+        strict_mode_violations: strict_violation_messages(
+            &result.strict_mode_violations,
+        ),
+        budget_overruns: &result.budget_overruns,
+        expiry_warnings: &result.expiry_warnings,
+        unused_suppressions: &result.unused_suppressions,
+    };
+    to_json(&report)
+}
+
+// Renders the violations `update` recorded as JSON, along with any strict mode
+// violations that it cannot record.
+pub(crate) fn update_report(violations: &[&Violation]) -> String {
+    let (strict, recordable): (Vec<&Violation>, Vec<&Violation>) =
+        violations.iter().partition(|v| v.identifier.strict);
+    let report = UpdateReport {
+        recorded_violations: json_violations(recordable, |_| None),
+        strict_mode_violations: strict_violation_messages(
+            strict.iter().map(|v| &v.identifier),
+        ),
+    };
+    to_json(&report)
+}
+
+// Renders the changes `update --dry-run` would make as JSON, keyed by pack name.
+pub(crate) fn dry_run_report(diff: &PackageTodoDiff) -> String {
+    to_json(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::packs::checker::StaleReason;
+    use crate::packs::SourceLocation;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_check_report() {
+        let violation = Violation {
+            message: String::from(
+                "\x1b[36mpacks/foo/app/services/foo.rb\x1b[0m:3:4\nDependency violation: `::Bar` belongs to `packs/bar`",
+            ),
+            identifier: ViolationIdentifier {
+                violation_type: String::from("dependency"),
+                strict: false,
+                warning: false,
+                file: String::from("packs/foo/app/services/foo.rb"),
+                constant_name: String::from("::Bar"),
+                referencing_pack_name: String::from("packs/foo"),
+                defining_pack_name: String::from("packs/bar"),
+            },
+            source_location: SourceLocation { line: 3, column: 4 },
+        };
+        let stale = StaleViolation {
+            identifier: ViolationIdentifier {
+                file: String::from("packs/foo/app/services/old.rb"),
+                ..violation.identifier.clone()
+            },
+            reason: StaleReason::FileDeleted,
+        };
+        let warning = Violation {
+            message: String::from(
+                "packs/foo/app/services/foo.rb:5:4\nPrivacy violation: `::Bar::Secret` is private to `packs/bar`",
+            ),
+            identifier: ViolationIdentifier {
+                violation_type: String::from("privacy"),
+                warning: true,
+                constant_name: String::from("::Bar::Secret"),
+                ..violation.identifier.clone()
+            },
+            source_location: SourceLocation { line: 5, column: 4 },
+        };
+        let result = CheckAllResult {
+            reportable_violations: [violation.clone()].into(),
+            stale_violations: vec![stale],
+            strict_mode_violations: vec![],
+            blames: HashMap::from([(
+                violation.location_key(),
+                Blame {
+                    commit: String::from("8f2c1d0e9b7a"),
+                    author: String::from("Jane Doe"),
+                    date: String::from("2023-11-14"),
+                },
+            )]),
+            budget_overruns: vec![],
+            expiry_warnings: vec![],
+            warning_violations: vec![warning],
+            unused_suppressions: vec![],
+        };
+
+        let expected = r#"{
+  "violations": [
+    {
+      "violation_type": "dependency",
+      "strict": false,
+      "warning": false,
+      "file": "packs/foo/app/services/foo.rb",
+      "constant_name": "::Bar",
+      "referencing_pack_name": "packs/foo",
+      "defining_pack_name": "packs/bar",
+      "line": 3,
+      "column": 4,
+      "message": "packs/foo/app/services/foo.rb:3:4\nDependency violation: `::Bar` belongs to `packs/bar`",
+      "blame": {
+        "commit": "8f2c1d0e9b7a",
+        "author": "Jane Doe",
+        "date": "2023-11-14"
+      }
+    }
+  ],
+  "warnings": [
+    {
+      "violation_type": "privacy",
+      "strict": false,
+      "warning": true,
+      "file": "packs/foo/app/services/foo.rb",
+      "constant_name": "::Bar::Secret",
+      "referencing_pack_name": "packs/foo",
+      "defining_pack_name": "packs/bar",
+      "line": 5,
+      "column": 4,
+      "message": "packs/foo/app/services/foo.rb:5:4\nPrivacy violation: `::Bar::Secret` is private to `packs/bar`"
+    }
+  ],
+  "violations_by_author": [
+    {
+      "author": "Jane Doe",
+      "count": 1
+    }
+  ],
+  "stale_violations": [
+    {
+      "violation_type": "dependency",
+      "strict": false,
+      "warning": false,
+      "file": "packs/foo/app/services/old.rb",
+      "constant_name": "::Bar",
+      "referencing_pack_name": "packs/foo",
+      "defining_pack_name": "packs/bar",
+      "reason": "file_deleted"
+    }
+  ],
+  "strict_mode_violations": [],
+  "budget_overruns": [],
+  "expiry_warnings": [],
+  "unused_suppressions": []
+}"#;
+        assert_eq!(check_report(&result), expected);
+    }
+}
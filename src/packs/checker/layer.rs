@@ -1,44 +1,143 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use super::output_helper::print_reference_location;
-use super::pack_checker::PackChecker;
+use super::pack_checker::{
+    enforced_pack_dependencies, PackChecker, ViolationType,
+};
 use super::{CheckerInterface, ValidatorInterface};
 use crate::packs::checker::Reference;
 use crate::packs::pack::{CheckerSetting, Pack};
+use crate::packs::raw_configuration::RawLayers;
 use crate::packs::{Configuration, Violation};
 use anyhow::{bail, Result};
 
 #[derive(Default, Debug, Clone)]
 pub struct Layers {
     pub layers: Vec<String>,
+    // The layers each layer may depend on directly, when layers are declared as a
+    // graph rather than an ordered list
+    pub allowed_layers: Option<HashMap<String, Vec<String>>>,
 }
 
 const VIOLATION_TYPE: &str = "layer";
 const VIOLATION_NAME: &str = "Layer";
 
+impl From<RawLayers> for Layers {
+    fn from(raw_layers: RawLayers) -> Self {
+        match raw_layers {
+            RawLayers::Ordered(layers) => Layers {
+                layers,
+                allowed_layers: None,
+            },
+            RawLayers::Graph(graph) => Layers {
+                layers: graph.keys().cloned().collect(),
+                allowed_layers: Some(
+                    graph
+                        .into_iter()
+                        .map(|(layer, allowed)| {
+                            (layer, allowed.unwrap_or_default())
+                        })
+                        .collect(),
+                ),
+            },
+        }
+    }
+}
+
 impl Layers {
+    // An ordered list is a graph in which each layer may depend on the next one
+    fn direct_dependencies(&self, layer: &str) -> Vec<&String> {
+        match &self.allowed_layers {
+            Some(allowed_layers) => allowed_layers
+                .get(layer)
+                .map(|allowed| allowed.iter().collect())
+                .unwrap_or_default(),
+            None => self
+                .layers
+                .iter()
+                .skip_while(|ordered_layer| *ordered_layer != layer)
+                .nth(1)
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    // The layers `layer` can reach through its dependencies, not including itself
+    // unless there is a cycle
+    fn reachable_layers(&self, layer: &str) -> Vec<&String> {
+        let mut reachable: Vec<&String> = vec![];
+        let mut seen: HashSet<&String> = HashSet::new();
+        let mut queue: VecDeque<&String> =
+            self.direct_dependencies(layer).into();
+        while let Some(next) = queue.pop_front() {
+            if seen.insert(next) {
+                reachable.push(next);
+                queue.extend(self.direct_dependencies(next));
+            }
+        }
+        reachable
+    }
+
     fn can_depend_on(
         &self,
         referencing_layer: &String,
         defining_layer: &String,
     ) -> Result<bool> {
-        let referencing_layer_index = self
-            .layers
-            .iter()
-            .position(|layer| layer == referencing_layer);
+        if !self.layers.contains(referencing_layer)
+            || !self.layers.contains(defining_layer)
+        {
+            bail!("Could not find one of layer `{}` or layer `{}` in `packwerk.yml`",
+                referencing_layer, defining_layer)
+        }
+
+        Ok(referencing_layer == defining_layer
+            || self
+                .reachable_layers(referencing_layer)
+                .contains(&defining_layer))
+    }
 
-        let defining_layer_index =
-            self.layers.iter().position(|layer| layer == defining_layer);
+    // Explains which layers a layer can depend on, for layers declared as a graph
+    fn reachability_description(&self, layer: &str) -> Option<String> {
+        self.allowed_layers.as_ref()?;
+        let mut reachable: Vec<&String> = self.reachable_layers(layer);
+        reachable.sort();
+        Some(if reachable.is_empty() {
+            format!("`{}` cannot depend on other layers", layer)
+        } else {
+            format!(
+                "`{}` can only depend on {}",
+                layer,
+                reachable
+                    .iter()
+                    .map(|layer| format!("`{}`", layer))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+    }
 
-        match (referencing_layer_index, defining_layer_index) {
-            (Some(referencing_layer_index), Some(defining_layer_index)) => {
-                Ok(referencing_layer_index <= defining_layer_index)
+    fn validate_graph(&self) -> Vec<String> {
+        let Some(allowed_layers) = &self.allowed_layers else {
+            return vec![];
+        };
+        let mut error_messages = vec![];
+        for layer in &self.layers {
+            for allowed in &allowed_layers[layer] {
+                if !self.layers.contains(allowed) {
+                    error_messages.push(format!(
+                        "Layer `{}` in `packwerk.yml` may depend on `{}`, which is not a layer defined in `packwerk.yml`",
+                        layer, allowed
+                    ));
+                }
             }
-            _ => {
-                bail!("Could not find one of layer `{}` or layer `{}` in `packwerk.yml`",
-                    referencing_layer, defining_layer)
+            if self.reachable_layers(layer).contains(&layer) {
+                error_messages.push(format!(
+                    "Layer `{}` in `packwerk.yml` can reach itself through the layers it may depend on, but layers must not have cycles",
+                    layer
+                ));
             }
         }
+        error_messages
     }
 
     fn pack_enforces_layers<'a>(&self, pack: &'a Pack) -> &'a CheckerSetting {
@@ -85,7 +184,30 @@ impl Checker {
 
 impl ValidatorInterface for Checker {
     fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
-        let mut error_messages: Vec<String> = vec![];
+        let mut error_messages: Vec<String> = self.layers.validate_graph();
+
+        for pack_dependency in
+            enforced_pack_dependencies(configuration, ViolationType::Layer)
+        {
+            let (from_pack, to_pack) =
+                (pack_dependency.from_pack, pack_dependency.to_pack);
+            let (Some(from_layer), Some(to_layer)) =
+                (&from_pack.layer, &to_pack.layer)
+            else {
+                continue;
+            };
+            // Unknown layers are reported by validate_pack
+            if let Ok(false) = self.layers.can_depend_on(from_layer, to_layer) {
+                error_messages.push(format!(
+                    "Invalid 'dependencies' option in '{}'. `{}` (whose layer is `{}`) cannot depend on `{}` (whose layer is `{}`)",
+                    from_pack.relative_yml().to_string_lossy(),
+                    from_pack.name,
+                    from_layer,
+                    to_pack.name,
+                    to_layer,
+                ));
+            }
+        }
 
         for pack in &configuration.pack_set.packs {
             if let Some(error_message) = self.validate_pack(pack) {
@@ -130,7 +252,7 @@ impl CheckerInterface for Checker {
 
                 let loc = print_reference_location(reference);
 
-                let message = format!(
+                let mut message = format!(
                     "{}{} violation: `{}` belongs to `{}` (whose layer is `{}`) cannot be accessed from `{}` (whose layer is `{}`)",
                     loc,
                     self.layers.violation_name(),
@@ -140,10 +262,16 @@ impl CheckerInterface for Checker {
                     pack_checker.referencing_pack.name,
                     referencing_layer,
                 );
+                if let Some(description) =
+                    self.layers.reachability_description(referencing_layer)
+                {
+                    message.push_str(&format!(", as {}", description));
+                }
 
                 Ok(Some(Violation {
                     message,
                     identifier: pack_checker.violation_identifier(),
+                    source_location: reference.source_location.clone(),
                 }))
             }
             _ => Ok(None),
@@ -181,6 +309,7 @@ mod tests {
                     String::from("product"),
                     String::from("utilities"),
                 ],
+                ..Layers::default()
             },
         }
     }
@@ -322,6 +451,7 @@ mod tests {
                         .map(|s| s.to_string())
                         .collect(),
                     reason: "deprecated".to_string(),
+                    expires_on: None,
                 }]),
                 ..default_referencing_pack()
             },
@@ -359,6 +489,7 @@ mod tests {
         let checker = Checker {
             layers: Layers {
                 layers: config_layers,
+                ..Layers::default()
             },
         };
         checker.validate(&configuration)
@@ -481,6 +612,7 @@ mod tests {
                     String::from("product"),
                     String::from("utilities"),
                 ],
+                ..Layers::default()
             },
         };
 
@@ -496,4 +628,109 @@ mod tests {
         ];
         assert_eq!(errors, expected_errors);
     }
+
+    fn graph_layers(yml: &str) -> Layers {
+        Layers::from(serde_yaml::from_str::<RawLayers>(yml).unwrap())
+    }
+
+    #[test]
+    fn test_graph_layers_use_reachability() -> anyhow::Result<()> {
+        let layers = graph_layers(
+            "
+app: [checkout, search]
+checkout: [platform]
+search: [platform]
+platform: [utilities]
+utilities:
+",
+        );
+        let layer = |name: &str| name.to_owned();
+
+        assert!(layers.can_depend_on(&layer("app"), &layer("utilities"))?);
+        assert!(layers.can_depend_on(&layer("checkout"), &layer("platform"))?);
+        assert!(layers.can_depend_on(&layer("search"), &layer("search"))?);
+        assert!(!layers.can_depend_on(&layer("checkout"), &layer("search"))?);
+        assert!(!layers.can_depend_on(&layer("platform"), &layer("app"))?);
+        assert!(layers
+            .can_depend_on(&layer("app"), &layer("unknown"))
+            .is_err());
+        assert_eq!(
+            layers.reachability_description("checkout"),
+            Some(String::from(
+                "`checkout` can only depend on `platform`, `utilities`"
+            ))
+        );
+        assert_eq!(
+            layers.reachability_description("utilities"),
+            Some(String::from("`utilities` cannot depend on other layers"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ordered_layers_use_reachability() -> anyhow::Result<()> {
+        let layers = Layers::from(RawLayers::Ordered(vec![
+            String::from("product"),
+            String::from("platform"),
+            String::from("utilities"),
+        ]));
+        let layer = |name: &str| name.to_owned();
+
+        assert!(layers.can_depend_on(&layer("product"), &layer("utilities"))?);
+        assert!(!layers.can_depend_on(&layer("utilities"), &layer("platform"))?);
+        assert_eq!(layers.reachability_description("product"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_graph_layers() {
+        let layers = graph_layers(
+            "
+checkout: [platform, billing]
+platform: [checkout]
+",
+        );
+
+        assert_eq!(
+            layers.validate_graph(),
+            vec![
+                "Layer `checkout` in `packwerk.yml` may depend on `billing`, which is not a layer defined in `packwerk.yml`".to_string(),
+                "Layer `checkout` in `packwerk.yml` can reach itself through the layers it may depend on, but layers must not have cycles".to_string(),
+                "Layer `platform` in `packwerk.yml` can reach itself through the layers it may depend on, but layers must not have cycles".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reference_is_a_graph_layer_violation() -> anyhow::Result<()> {
+        let mut test_checker = TestChecker {
+            reference: None,
+            configuration: None,
+            referenced_constant_name: Some(String::from("::Bar")),
+            defining_pack: Some(Pack {
+                name: "packs/bar".to_owned(),
+                layer: Some("search".to_string()),
+                ..default_defining_pack()
+            }),
+            referencing_pack: Pack {
+                name: "packs/foo".to_owned(),
+                enforce_layers: Some(CheckerSetting::True),
+                layer: Some("checkout".to_string()),
+                ..default_referencing_pack()
+            },
+            expected_violation: Some(build_expected_violation(
+                "packs/foo/app/services/foo.rb:3:1\nLayer violation: `::Bar` belongs to `packs/bar` (whose layer is `search`) cannot be accessed from `packs/foo` (whose layer is `checkout`), as `checkout` can only depend on `platform`".to_string(),
+                "layer".to_string(), false)),
+        };
+        let checker = Checker {
+            layers: graph_layers(
+                "
+checkout: [platform]
+search: [platform]
+platform: []
+",
+            ),
+        };
+        test_check(&checker, &mut test_checker)
+    }
 }
@@ -0,0 +1,561 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::packs::bin_locater;
+use crate::packs::package_todo::{package_todo_path, PackageTodoDiff};
+
+use super::{
+    build_strict_violation_message, CheckAllResult, StaleViolation, Violation,
+    ViolationIdentifier,
+};
+
+// Renders `check` results as markdown, e.g. for posting as a pull request comment.
+pub(crate) fn check_report(result: &CheckAllResult) -> String {
+    let mut out = String::from("## packs check\n\n");
+
+    if !result.expiry_warnings.is_empty() {
+        out.push_str("### Expiry warnings\n\n");
+        for warning in &result.expiry_warnings {
+            let _ = writeln!(out, "- {}", warning);
+        }
+        out.push('\n');
+    }
+
+    if !result.warning_violations.is_empty() {
+        write_warning_section(&mut out, &result.warning_violations);
+    }
+
+    if !result.has_violations() {
+        out.push_str("No violations detected!\n");
+        return out;
+    }
+
+    let _ = writeln!(
+        out,
+        "**{}** new violation(s), **{}** stale todo entr(ies), **{}** strict mode violation(s)\n",
+        result.reportable_violations.len(),
+        result.stale_violations.len(),
+        result.strict_mode_violations.len(),
+    );
+
+    let with_blame = !result.blames.is_empty();
+    let new_violations: Vec<&Violation> =
+        result.reportable_violations.iter().collect();
+    for (pack_name, violations) in group_by_referencing_pack(&new_violations) {
+        let _ = writeln!(
+            out,
+            "<details>\n<summary><code>{}</code>: {} new violation(s)</summary>\n",
+            pack_name,
+            violations.len()
+        );
+        write_violation_table(&mut out, &violations, |violation| {
+            if with_blame {
+                let blame = match result.blames.get(&violation.location_key()) {
+                    Some(blame) if blame.is_committed() => format!(
+                        "`{}` {} ({})",
+                        blame.short_commit(),
+                        blame.author,
+                        blame.date
+                    ),
+                    Some(_) => String::from("Not Committed Yet"),
+                    None => String::new(),
+                };
+                Some(blame)
+            } else {
+                None
+            }
+        });
+        out.push_str("\n</details>\n\n");
+    }
+
+    if with_blame {
+        out.push_str("### New violations by author\n\n| Author | New violations |\n| --- | --- |\n");
+        for (author, count) in result.violation_counts_by_author() {
+            let _ = writeln!(out, "| {} | {} |", escape_cell(author), count);
+        }
+        out.push('\n');
+    }
+
+    if !result.stale_violations.is_empty() {
+        write_stale_section(&mut out, &result.stale_violations);
+    }
+
+    if !result.strict_mode_violations.is_empty() {
+        write_strict_section(
+            &mut out,
+            result.strict_mode_violations.iter().collect(),
+        );
+    }
+
+    if !result.budget_overruns.is_empty() {
+        out.push_str("### Violation budgets\n\n");
+        for overrun in &result.budget_overruns {
+            let _ = writeln!(out, "- {}", overrun);
+        }
+        out.push('\n');
+    }
+
+    if !result.unused_suppressions.is_empty() {
+        out.push_str("### Unused suppressions\n\nThese suppression comments no longer suppress any violations and should be removed.\n\n");
+        for unused in &result.unused_suppressions {
+            let location = location_link(&unused.file, unused.line);
+            let _ = match &unused.violation_type {
+                Some(violation_type) => writeln!(
+                    out,
+                    "- {} does not suppress any {} violations",
+                    location, violation_type
+                ),
+                None => writeln!(
+                    out,
+                    "- {} names no violation types, so it does not suppress anything",
+                    location
+                ),
+            };
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+// Renders the outcome of `update` as markdown: the violations now recorded in each pack's
+// package_todo.yml, followed by any strict mode violations that `update` cannot record.
+pub(crate) fn update_report(violations: &[&Violation]) -> String {
+    let mut out = String::from(
+        "## packs update\n\nSuccessfully updated package_todo.yml files!\n\n",
+    );
+
+    let (strict, recordable): (Vec<&Violation>, Vec<&Violation>) =
+        violations.iter().partition(|v| v.identifier.strict);
+
+    for (pack_name, violations) in group_by_referencing_pack(&recordable) {
+        let _ = writeln!(
+            out,
+            "<details>\n<summary><code>{}</code>: {} recorded violation(s)</summary>\n",
+            pack_name,
+            violations.len()
+        );
+        write_violation_table(&mut out, &violations, |_| None);
+        out.push_str("\n</details>\n\n");
+    }
+
+    if !strict.is_empty() {
+        write_strict_section(
+            &mut out,
+            strict.iter().map(|v| &v.identifier).collect(),
+        );
+    }
+
+    out
+}
+
+// Renders the changes `update --dry-run` would make as a diff per package_todo.yml file.
+pub(crate) fn dry_run_report(diff: &PackageTodoDiff) -> String {
+    let mut out = String::from("## packs update --dry-run\n\n");
+
+    if diff.changes_by_pack.is_empty() {
+        out.push_str("No changes to package_todo.yml files\n");
+        return out;
+    }
+
+    for (pack_name, changes) in &diff.changes_by_pack {
+        let _ = writeln!(
+            out,
+            "<details>\n<summary><code>{}</code>: {} addition(s), {} removal(s)</summary>\n\n```diff\n{}```\n\n</details>\n",
+            package_todo_path(pack_name),
+            changes.additions.len(),
+            changes.removals.len(),
+            changes,
+        );
+    }
+
+    out
+}
+
+fn group_by_referencing_pack<'a>(
+    violations: &[&'a Violation],
+) -> BTreeMap<&'a str, Vec<&'a Violation>> {
+    let mut grouped: BTreeMap<&str, Vec<&Violation>> = BTreeMap::new();
+    for violation in violations {
+        grouped
+            .entry(violation.identifier.referencing_pack_name.as_str())
+            .or_default()
+            .push(violation);
+    }
+    for violations in grouped.values_mut() {
+        violations.sort_by(|a, b| {
+            a.identifier
+                .file
+                .cmp(&b.identifier.file)
+                .then_with(|| {
+                    a.source_location.line.cmp(&b.source_location.line)
+                })
+                .then_with(|| {
+                    a.identifier.constant_name.cmp(&b.identifier.constant_name)
+                })
+                .then_with(|| {
+                    a.identifier
+                        .violation_type
+                        .cmp(&b.identifier.violation_type)
+                })
+        });
+    }
+    grouped
+}
+
+// `extra_column` returns the contents of an optional trailing "Introduced by" column.
+fn write_violation_table<F>(
+    out: &mut String,
+    violations: &[&Violation],
+    extra_column: F,
+) where
+    F: Fn(&Violation) -> Option<String>,
+{
+    let with_extra_column = violations
+        .first()
+        .is_some_and(|violation| extra_column(violation).is_some());
+
+    if with_extra_column {
+        out.push_str(
+            "| Type | Constant | Defining pack | Location | Introduced by |\n",
+        );
+        out.push_str("| --- | --- | --- | --- | --- |\n");
+    } else {
+        out.push_str("| Type | Constant | Defining pack | Location |\n");
+        out.push_str("| --- | --- | --- | --- |\n");
+    }
+
+    for violation in violations {
+        let identifier = &violation.identifier;
+        let _ = write!(
+            out,
+            "| {} | `{}` | `{}` | {} |",
+            identifier.violation_type,
+            identifier.constant_name,
+            identifier.defining_pack_name,
+            location_link(&identifier.file, violation.source_location.line),
+        );
+        if let Some(extra) = extra_column(violation) {
+            let _ = write!(out, " {} |", escape_cell(&extra));
+        }
+        out.push('\n');
+    }
+}
+
+fn write_warning_section(out: &mut String, warnings: &[Violation]) {
+    let warnings: Vec<&Violation> = warnings.iter().collect();
+    out.push_str("### Warnings\n\nThese violations come from checkers set to `warn` and do not fail `check`.\n\n");
+    for (pack_name, violations) in group_by_referencing_pack(&warnings) {
+        let _ = writeln!(
+            out,
+            "<details>\n<summary><code>{}</code>: {} warning(s)</summary>\n",
+            pack_name,
+            violations.len()
+        );
+        write_violation_table(out, &violations, |_| None);
+        out.push_str("\n</details>\n\n");
+    }
+}
+
+fn write_stale_section(out: &mut String, stale: &[StaleViolation]) {
+    let mut stale: Vec<&StaleViolation> = stale.iter().collect();
+    stale.sort_by(|a, b| {
+        let (a, b) = (&a.identifier, &b.identifier);
+        a.referencing_pack_name
+            .cmp(&b.referencing_pack_name)
+            .then_with(|| a.defining_pack_name.cmp(&b.defining_pack_name))
+            .then_with(|| a.constant_name.cmp(&b.constant_name))
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.violation_type.cmp(&b.violation_type))
+    });
+
+    let _ = writeln!(
+        out,
+        "### Stale todo entries\n\nThese entries are recorded in package_todo.yml but no longer occur. Run `{} update` to remove them.\n",
+        bin_locater::packs_bin_name(),
+    );
+    out.push_str(
+        "| Pack | Type | Constant | Defining pack | File | Reason |\n",
+    );
+    out.push_str("| --- | --- | --- | --- | --- | --- |\n");
+    for v in stale {
+        let _ = writeln!(
+            out,
+            "| `{}` | {} | `{}` | `{}` | {} | {} |",
+            v.identifier.referencing_pack_name,
+            v.identifier.violation_type,
+            v.identifier.constant_name,
+            v.identifier.defining_pack_name,
+            location_link(&v.identifier.file, 0),
+            v.reason,
+        );
+    }
+    out.push('\n');
+}
+
+fn write_strict_section(out: &mut String, strict: Vec<&ViolationIdentifier>) {
+    let mut messages: Vec<String> = strict
+        .into_iter()
+        .map(build_strict_violation_message)
+        .collect();
+    messages.sort();
+    messages.dedup();
+
+    out.push_str("### Strict mode violations\n\nThese violations cannot be recorded in package_todo.yml and must be fixed.\n\n");
+    for message in messages {
+        let _ = writeln!(out, "- {}", message);
+    }
+    out.push('\n');
+}
+
+// Links are relative to the project root so they resolve against the repository on GitHub.
+// ERB references have no line number (0), so they link to the file only.
+fn location_link(file: &str, line: usize) -> String {
+    if line == 0 {
+        format!("[{}]({})", escape_cell(file), file)
+    } else {
+        format!("[{}:{}]({}#L{})", escape_cell(file), line, file, line)
+    }
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+    use crate::packs::blame::Blame;
+    use crate::packs::checker::budget::BudgetOverrun;
+    use crate::packs::checker::StaleReason;
+    use crate::packs::package_todo::PackageTodoChanges;
+    use crate::packs::SourceLocation;
+    use pretty_assertions::assert_eq;
+
+    fn violation(
+        violation_type: &str,
+        file: &str,
+        line: usize,
+        strict: bool,
+    ) -> Violation {
+        Violation {
+            message: String::from("unused in markdown output"),
+            identifier: ViolationIdentifier {
+                violation_type: violation_type.to_owned(),
+                strict,
+                warning: false,
+                file: file.to_owned(),
+                constant_name: String::from("::Bar"),
+                referencing_pack_name: String::from("packs/foo"),
+                defining_pack_name: String::from("packs/bar"),
+            },
+            source_location: SourceLocation { line, column: 1 },
+        }
+    }
+
+    #[test]
+    fn test_check_report_without_violations() {
+        let result = CheckAllResult {
+            reportable_violations: HashSet::new(),
+            stale_violations: vec![],
+            strict_mode_violations: vec![],
+            blames: HashMap::new(),
+            budget_overruns: vec![],
+            expiry_warnings: vec![],
+            warning_violations: vec![],
+            unused_suppressions: vec![],
+        };
+        assert_eq!(
+            check_report(&result),
+            "## packs check\n\nNo violations detected!\n"
+        );
+    }
+
+    #[test]
+    fn test_check_report_with_warnings() {
+        let warning = Violation {
+            identifier: ViolationIdentifier {
+                warning: true,
+                ..violation("dependency", "packs/foo/app/foo.rb", 3, false)
+                    .identifier
+            },
+            ..violation("dependency", "packs/foo/app/foo.rb", 3, false)
+        };
+        let result = CheckAllResult {
+            reportable_violations: HashSet::new(),
+            stale_violations: vec![],
+            strict_mode_violations: vec![],
+            blames: HashMap::new(),
+            budget_overruns: vec![],
+            expiry_warnings: vec![],
+            warning_violations: vec![warning],
+            unused_suppressions: vec![],
+        };
+
+        let expected = "\
+## packs check
+
+### Warnings
+
+These violations come from checkers set to `warn` and do not fail `check`.
+
+<details>
+<summary><code>packs/foo</code>: 1 warning(s)</summary>
+
+| Type | Constant | Defining pack | Location |
+| --- | --- | --- | --- |
+| dependency | `::Bar` | `packs/bar` | [packs/foo/app/foo.rb:3](packs/foo/app/foo.rb#L3) |
+
+</details>
+
+No violations detected!
+";
+        assert_eq!(check_report(&result), expected);
+    }
+
+    #[test]
+    fn test_check_report() {
+        let dependency =
+            violation("dependency", "packs/foo/app/services/foo.rb", 3, false);
+        let privacy =
+            violation("privacy", "packs/foo/app/views/foo.erb", 0, false);
+        let strict = violation("privacy", "packs/foo/app/foo.rb", 7, true);
+        let stale = StaleViolation {
+            identifier: ViolationIdentifier {
+                file: String::from("packs/foo/app/services/old.rb"),
+                ..dependency.identifier.clone()
+            },
+            reason: StaleReason::FileDeleted,
+        };
+        let result = CheckAllResult {
+            reportable_violations: [dependency.clone(), privacy].into(),
+            stale_violations: vec![stale],
+            strict_mode_violations: vec![strict.identifier],
+            blames: [(
+                dependency.location_key(),
+                Blame {
+                    commit: String::from("8f2c1d0e9b7a"),
+                    author: String::from("Jane Doe"),
+                    date: String::from("2023-11-14"),
+                },
+            )]
+            .into(),
+            budget_overruns: vec![BudgetOverrun {
+                pack_name: String::from("packs/foo"),
+                violation_type: None,
+                recorded: 4,
+                budget: 3,
+            }],
+            expiry_warnings: vec![],
+            warning_violations: vec![],
+            unused_suppressions: vec![],
+        };
+
+        let expected = "\
+## packs check
+
+**2** new violation(s), **1** stale todo entr(ies), **1** strict mode violation(s)
+
+<details>
+<summary><code>packs/foo</code>: 2 new violation(s)</summary>
+
+| Type | Constant | Defining pack | Location | Introduced by |
+| --- | --- | --- | --- | --- |
+| dependency | `::Bar` | `packs/bar` | [packs/foo/app/services/foo.rb:3](packs/foo/app/services/foo.rb#L3) | `8f2c1d0e` Jane Doe (2023-11-14) |
+| privacy | `::Bar` | `packs/bar` | [packs/foo/app/views/foo.erb](packs/foo/app/views/foo.erb) |  |
+
+</details>
+
+### New violations by author
+
+| Author | New violations |
+| --- | --- |
+| Jane Doe | 1 |
+| Unknown | 1 |
+
+### Stale todo entries
+
+These entries are recorded in package_todo.yml but no longer occur. Run `packs update` to remove them.
+
+| Pack | Type | Constant | Defining pack | File | Reason |
+| --- | --- | --- | --- | --- | --- |
+| `packs/foo` | dependency | `::Bar` | `packs/bar` | [packs/foo/app/services/old.rb](packs/foo/app/services/old.rb) | the file was deleted |
+
+### Strict mode violations
+
+These violations cannot be recorded in package_todo.yml and must be fixed.
+
+- packs/foo cannot have privacy violations on packs/bar because strict mode is enabled for privacy violations in the enforcing pack's package.yml file
+
+### Violation budgets
+
+- packs/foo has 4 recorded violation(s), which exceeds its violation_budget of 3
+
+";
+        assert_eq!(check_report(&result), expected);
+    }
+
+    #[test]
+    fn test_dry_run_report() {
+        let addition =
+            violation("dependency", "packs/foo/app/services/foo.rb", 3, false);
+        let diff = PackageTodoDiff {
+            changes_by_pack: [(
+                String::from("packs/foo"),
+                PackageTodoChanges {
+                    additions: vec![addition.identifier],
+                    removals: vec![],
+                    occurrence_changes: vec![],
+                },
+            )]
+            .into(),
+        };
+
+        let expected = "\
+## packs update --dry-run
+
+<details>
+<summary><code>packs/foo/package_todo.yml</code>: 1 addition(s), 0 removal(s)</summary>
+
+```diff
++ dependency `::Bar` (defined in `packs/bar`) in packs/foo/app/services/foo.rb
+```
+
+</details>
+
+";
+        assert_eq!(dry_run_report(&diff), expected);
+    }
+
+    #[test]
+    fn test_update_report() {
+        let recorded =
+            violation("dependency", "packs/foo/app/services/foo.rb", 3, false);
+        let strict = violation("privacy", "packs/foo/app/foo.rb", 7, true);
+
+        let expected = "\
+## packs update
+
+Successfully updated package_todo.yml files!
+
+<details>
+<summary><code>packs/foo</code>: 1 recorded violation(s)</summary>
+
+| Type | Constant | Defining pack | Location |
+| --- | --- | --- | --- |
+| dependency | `::Bar` | `packs/bar` | [packs/foo/app/services/foo.rb:3](packs/foo/app/services/foo.rb#L3) |
+
+</details>
+
+### Strict mode violations
+
+These violations cannot be recorded in package_todo.yml and must be fixed.
+
+- packs/foo cannot have privacy violations on packs/bar because strict mode is enabled for privacy violations in the enforcing pack's package.yml file
+
+";
+        assert_eq!(update_report(&[&recorded, &strict]), expected);
+    }
+}
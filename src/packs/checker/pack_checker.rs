@@ -1,6 +1,10 @@
+use std::{collections::HashMap, path::PathBuf};
+
 use crate::packs::{
     pack::{CheckerSetting, Pack},
-    Configuration,
+    pack_set::PackDependency,
+    parsing::ruby::parse_utils::extract_sigils_from_contents,
+    Configuration, Sigil, SigilValue,
 };
 
 use super::{reference::Reference, ViolationIdentifier};
@@ -20,6 +24,7 @@ enum ViolationDirection {
 
 #[derive(Debug, Clone, Copy)]
 pub enum ViolationType {
+    ArchitectureRule,
     Dependency,
     FolderPrivacy,
     Layer,
@@ -30,6 +35,7 @@ pub enum ViolationType {
 impl From<&str> for ViolationType {
     fn from(s: &str) -> Self {
         match s {
+            "architecture_rule" => ViolationType::ArchitectureRule,
             "dependency" => ViolationType::Dependency,
             "folder_privacy" => ViolationType::FolderPrivacy,
             "layer" => ViolationType::Layer,
@@ -43,6 +49,7 @@ impl From<&str> for ViolationType {
 impl From<ViolationType> for &str {
     fn from(violation_type: ViolationType) -> &'static str {
         match violation_type {
+            ViolationType::ArchitectureRule => "architecture_rule",
             ViolationType::Dependency => "dependency",
             ViolationType::FolderPrivacy => "folder_privacy",
             ViolationType::Layer => "layer",
@@ -52,6 +59,120 @@ impl From<ViolationType> for &str {
     }
 }
 
+impl ViolationType {
+    pub const ALL: [ViolationType; 6] = [
+        ViolationType::ArchitectureRule,
+        ViolationType::Dependency,
+        ViolationType::FolderPrivacy,
+        ViolationType::Layer,
+        ViolationType::Privacy,
+        ViolationType::Visibility,
+    ];
+
+    fn direction(&self) -> ViolationDirection {
+        match self {
+            ViolationType::ArchitectureRule
+            | ViolationType::Dependency
+            | ViolationType::Layer => ViolationDirection::Outgoing,
+            ViolationType::Privacy
+            | ViolationType::FolderPrivacy
+            | ViolationType::Visibility => ViolationDirection::Incoming,
+        }
+    }
+
+    // The pack whose package.yml setting applies to the violation
+    pub fn rules_pack_name<'b>(
+        &self,
+        violation_identifier: &'b ViolationIdentifier,
+    ) -> &'b str {
+        match self.direction() {
+            ViolationDirection::Outgoing => {
+                &violation_identifier.referencing_pack_name
+            }
+            ViolationDirection::Incoming => {
+                &violation_identifier.defining_pack_name
+            }
+        }
+    }
+
+    pub fn is_globally_disabled(&self, configuration: &Configuration) -> bool {
+        match self {
+            // Architecture rules are enforced whenever there are any
+            ViolationType::ArchitectureRule => {
+                configuration.architecture_rules.is_empty()
+            }
+            ViolationType::Dependency => {
+                configuration.disable_enforce_dependencies
+            }
+            ViolationType::FolderPrivacy => {
+                configuration.disable_enforce_folder_privacy
+            }
+            ViolationType::Layer => configuration.disable_enforce_layers,
+            ViolationType::Privacy => configuration.disable_enforce_privacy,
+            ViolationType::Visibility => {
+                configuration.disable_enforce_visibility
+            }
+        }
+    }
+}
+
+// The package.yml `dependencies` whose rules pack enforces the violation type, so that
+// validators can make sure the declared dependencies don't contradict the checkers.
+// Unknown dependencies are left to the dependency validator.
+pub(crate) fn enforced_pack_dependencies(
+    configuration: &Configuration,
+    violation_type: ViolationType,
+) -> Vec<PackDependency<'_>> {
+    if violation_type.is_globally_disabled(configuration) {
+        return vec![];
+    }
+    let Ok(pack_dependencies) =
+        configuration.pack_set.all_pack_dependencies(configuration)
+    else {
+        return vec![];
+    };
+    pack_dependencies
+        .into_iter()
+        .filter(|pack_dependency| {
+            let rules_pack = match violation_type.direction() {
+                ViolationDirection::Outgoing => pack_dependency.from_pack,
+                ViolationDirection::Incoming => pack_dependency.to_pack,
+            };
+            let setting = match violation_type {
+                ViolationType::ArchitectureRule => &Some(CheckerSetting::True),
+                ViolationType::Dependency => &rules_pack.enforce_dependencies,
+                ViolationType::FolderPrivacy => {
+                    &Some(rules_pack.enforce_folder_privacy().clone())
+                }
+                ViolationType::Layer => &rules_pack.enforce_layers,
+                ViolationType::Privacy => &rules_pack.enforce_privacy,
+                ViolationType::Visibility => &rules_pack.enforce_visibility,
+            };
+            pack_dependency.from_pack != pack_dependency.to_pack
+                && setting.as_ref().is_some_and(|setting| !setting.is_false())
+        })
+        .collect()
+}
+
+// Whether a violation is strict under the checker setting that applies to it. With
+// `strict_for_new_files`, only violations in files that don't appear yet in the
+// package_todo.yml that would record them are strict.
+pub(crate) fn is_strict_violation(
+    configuration: &Configuration,
+    setting: &CheckerSetting,
+    identifier: &ViolationIdentifier,
+) -> bool {
+    setting.is_strict()
+        || (setting.is_strict_for_new_files() && {
+            let pack_set = &configuration.pack_set;
+            !pack_set
+                .for_pack(pack_set.responsible_pack_name(identifier))
+                .is_ok_and(|pack| {
+                    pack.package_todo.records_file(&identifier.file)
+                })
+        })
+}
+
 impl<'a> PackChecker<'a> {
     pub fn new(
         configuration: &'a Configuration,
@@ -69,14 +190,7 @@ impl<'a> PackChecker<'a> {
     }
 
     fn violation_direction(&self) -> ViolationDirection {
-        match self.violation_type {
-            ViolationType::Dependency | ViolationType::Layer => {
-                ViolationDirection::Outgoing
-            }
-            ViolationType::Privacy
-            | ViolationType::FolderPrivacy
-            | ViolationType::Visibility => ViolationDirection::Incoming,
-        }
+        self.violation_type.direction()
     }
 
     pub fn checkable(&self) -> anyhow::Result<bool> {
@@ -99,7 +213,64 @@ impl<'a> PackChecker<'a> {
     }
 
     pub fn is_strict(&self) -> bool {
-        self.rules_checker_setting().is_strict()
+        is_strict_violation(
+            self.configuration,
+            self.rules_checker_setting(),
+            &self.violation_identifier_with_strict(false),
+        )
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.rules_checker_setting().is_warn()
+    }
+
+    // The value of a `# pack_<name>: <value>` sigil in the file that defines the
+    // referenced constant.
+    pub fn defining_file_sigil(
+        &self,
+        sigils: &HashMap<PathBuf, Vec<Sigil>>,
+        name: &str,
+    ) -> Option<SigilValue> {
+        let relative_file = self.reference.relative_defining_file.as_ref()?;
+        let absolute_file =
+            self.configuration.absolute_root.join(relative_file);
+        let find = |file_sigils: &[Sigil]| {
+            file_sigils
+                .iter()
+                .find(|sigil| sigil.name == name)
+                .map(|sigil| sigil.value.clone())
+        };
+
+        if let Some(file_sigils) = sigils.get(&absolute_file) {
+            return find(file_sigils);
+        }
+        // `sigils` only has the files that were checked. When only some files are checked,
+        // as editor integrations do, the defining file most likely wasn't one of them, so we
+        // read it here instead. We don't do this for a full check, to avoid reading every
+        // file twice.
+        if self.configuration.input_files_count > 0 {
+            let contents = std::fs::read_to_string(&absolute_file).ok()?;
+            return find(&extract_sigils_from_contents(&contents));
+        }
+        None
+    }
+
+    // The value of a `# pack_<name>: <value>` sigil in the referencing file, which is
+    // always one of the checked files.
+    pub fn referencing_file_sigil(
+        &self,
+        sigils: &HashMap<PathBuf, Vec<Sigil>>,
+        name: &str,
+    ) -> Option<SigilValue> {
+        let absolute_file = self
+            .configuration
+            .absolute_root
+            .join(&self.reference.relative_referencing_file);
+        sigils
+            .get(&absolute_file)?
+            .iter()
+            .find(|sigil| sigil.name == name)
+            .map(|sigil| sigil.value.clone())
     }
 
     fn defining_pack_name(&self) -> &str {
@@ -112,6 +283,7 @@ impl<'a> PackChecker<'a> {
 
     fn rules_checker_setting(&self) -> &CheckerSetting {
         match self.violation_type {
+            ViolationType::ArchitectureRule => &CheckerSetting::True,
             ViolationType::Dependency => self
                 .checker_setting_for(&self.rules_pack().enforce_dependencies),
             ViolationType::FolderPrivacy => {
@@ -130,21 +302,7 @@ impl<'a> PackChecker<'a> {
     }
 
     fn violation_globally_disabled(&self) -> bool {
-        match self.violation_type {
-            ViolationType::Dependency => {
-                self.configuration.disable_enforce_dependencies
-            }
-            ViolationType::FolderPrivacy => {
-                self.configuration.disable_enforce_folder_privacy
-            }
-            ViolationType::Layer => self.configuration.disable_enforce_layers,
-            ViolationType::Privacy => {
-                self.configuration.disable_enforce_privacy
-            }
-            ViolationType::Visibility => {
-                self.configuration.disable_enforce_visibility
-            }
-        }
+        self.violation_type.is_globally_disabled(self.configuration)
     }
 
     fn checker_setting_for(
@@ -175,15 +333,39 @@ impl<'a> PackChecker<'a> {
                 self.reference.relative_defining_file.as_ref().unwrap()
             }
         };
-        self.rules_pack()
-            .is_ignored(file_path, self.violation_type.into())
+        self.rules_pack().is_ignored(
+            file_path,
+            self.violation_type.into(),
+            self.configuration.today,
+        )
     }
 
     pub fn violation_identifier(&self) -> ViolationIdentifier {
+        self.violation_identifier_with_strict(self.is_strict())
+    }
+
+    // For checkers whose setting doesn't come from the rules pack, such as architecture
+    // rules, which each have their own `enforcement`
+    pub fn violation_identifier_for_setting(
+        &self,
+        setting: &CheckerSetting,
+    ) -> ViolationIdentifier {
+        let mut identifier = self.violation_identifier_with_strict(false);
+        identifier.strict =
+            is_strict_violation(self.configuration, setting, &identifier);
+        identifier.warning = setting.is_warn();
+        identifier
+    }
+
+    fn violation_identifier_with_strict(
+        &self,
+        strict: bool,
+    ) -> ViolationIdentifier {
         let violation_type: &str = self.violation_type.into();
         ViolationIdentifier {
             violation_type: violation_type.to_string(),
-            strict: self.is_strict(),
+            strict,
+            warning: self.is_warning(),
             file: self.reference.relative_referencing_file.clone(),
             constant_name: self.reference.constant_name.clone(),
             referencing_pack_name: self.referencing_pack.name.clone(),
@@ -1,14 +1,95 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::output_helper::print_reference_location;
 use super::pack_checker::PackChecker;
-use super::CheckerInterface;
+use super::{CheckerInterface, ValidatorInterface};
 use crate::packs::checker::Reference;
-use crate::packs::parsing::ruby;
-use crate::packs::{Configuration, Violation};
+use crate::packs::pack::Pack;
+use crate::packs::{get_constant_resolver, Configuration, Violation};
 
 pub struct Checker {}
 
+fn constant_segments(constant: &str) -> Vec<&str> {
+    constant
+        .trim_start_matches("::")
+        .split("::")
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+// `*` matches any characters within a single segment
+fn segment_matches(pattern: &str, segment: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == segment,
+        Some((prefix, rest)) => {
+            segment.starts_with(prefix)
+                && (0..=segment.len() - prefix.len()).any(|start| {
+                    segment.is_char_boundary(prefix.len() + start)
+                        && segment_matches(
+                            rest,
+                            &segment[prefix.len() + start..],
+                        )
+                })
+        }
+    }
+}
+
+// `**` matches zero or more segments
+fn segments_match(patterns: &[&str], segments: &[&str]) -> bool {
+    match patterns.split_first() {
+        None => segments.is_empty(),
+        Some((&"**", rest)) => (0..=segments.len())
+            .any(|skipped| segments_match(rest, &segments[skipped..])),
+        Some((pattern, rest)) => {
+            segments.split_first().is_some_and(|(segment, segments)| {
+                segment_matches(pattern, segment)
+                    && segments_match(rest, segments)
+            })
+        }
+    }
+}
+
+// Whether a private_constants or ignored_private_constants entry, which may contain
+// `*` and `**` wildcards, matches the fully qualified constant.
+fn constant_matches(pattern: &str, constant: &str) -> bool {
+    segments_match(&constant_segments(pattern), &constant_segments(constant))
+}
+
+// Whether the entry matches the constant or one of its enclosing namespaces, since
+// everything nested in a private constant is private too.
+pub(crate) fn constant_or_namespace_matches(
+    pattern: &str,
+    constant: &str,
+) -> bool {
+    let patterns = constant_segments(pattern);
+    let segments = constant_segments(constant);
+    (1..=segments.len()).any(|len| segments_match(&patterns, &segments[..len]))
+}
+
+fn unmatched_entries(
+    pack: &Pack,
+    key: &str,
+    entries: &HashSet<String>,
+    constants: &[&String],
+    matches: fn(&str, &str) -> bool,
+) -> Vec<String> {
+    entries
+        .iter()
+        .filter(|entry| {
+            !constants.iter().any(|constant| matches(entry, constant))
+        })
+        .map(|entry| {
+            format!(
+                "`{}` in the {} of {} does not match any constant defined in {}",
+                entry,
+                key,
+                pack.relative_yml().display(),
+                pack.name
+            )
+        })
+        .collect()
+}
+
 impl CheckerInterface for Checker {
     fn check(
         &self,
@@ -24,51 +105,23 @@ impl CheckerInterface for Checker {
         let defining_pack = pack_checker.defining_pack.unwrap();
         if defining_pack
             .ignored_private_constants
-            .contains(&reference.constant_name)
+            .iter()
+            .any(|ignored| constant_matches(ignored, &reference.constant_name))
         {
             return Ok(None);
         }
 
-        // This is a hack for now – we need to read package.yml file public_paths at some point,
-        // and probably find a better way to check if the constant is public
-        let public_folder = &defining_pack.public_folder();
-        let is_public = reference
-            .relative_defining_file
-            .as_ref()
-            .map(|relative_file| {
-                let absolute_file =
-                    configuration.absolute_root.join(relative_file);
-
-                // if configuration.input_files_count is greater than zero, we're just going to individually
-                // take the contents of the absolute file and call extract_sigils_from_contents on it to get the sigils
-                // and then check if a "public" sigil is contained. manual_read_of_defining_file_contains_sigil
-                // That's because we need to process a file to get the sigils, and if files are inputted, we likely didn't also input
-                // the file that defines the constant and has the sigil.
-                let manual_read_of_defining_file_contains_sigil =
-                    if configuration.input_files_count > 0 {
-                        if let Ok(contents) =
-                            std::fs::read_to_string(&absolute_file)
-                        {
-                            let sigils =
-                                ruby::parse_utils::extract_sigils_from_contents(
-                                    &contents,
-                                );
-
-                            sigils.iter().any(|sigil| sigil.name == "public")
-                        } else {
-                            false
-                        }
-                    } else {
-                        false
-                    };
-
-                // Check if the relative file starts with `public_folder` or the absolute file is in `sigils`
-                relative_file
-                    .starts_with(public_folder.to_string_lossy().as_ref())
-                    || sigils.contains_key(&absolute_file)
-                    || manual_read_of_defining_file_contains_sigil
-            })
-            .unwrap_or(false);
+        // A `pack_public` sigil in the defining file takes precedence over public_paths
+        // (or the public folder).
+        let is_public = match pack_checker
+            .defining_file_sigil(sigils, "public")
+            .and_then(|value| value.as_bool())
+        {
+            Some(is_public) => is_public,
+            None => reference.relative_defining_file.as_ref().is_some_and(
+                |relative_file| defining_pack.is_public_file(relative_file),
+            ),
+        };
 
         // Note this means that if the constant is ALSO in the list of private_constants,
         // it will be considered public.
@@ -81,20 +134,15 @@ impl CheckerInterface for Checker {
 
         let private_constants = &defining_pack.private_constants;
 
-        if !private_constants.is_empty() {
-            let constant_is_private =
-                private_constants.contains(&reference.constant_name);
-
-            let constant_is_in_private_namespace =
-                private_constants.iter().any(|private_constant| {
-                    let namespaced_constant =
-                        &format!("{}::", private_constant);
-                    reference.constant_name.starts_with(namespaced_constant)
-                });
-            dbg!(constant_is_private, constant_is_in_private_namespace);
-            if !constant_is_private && !constant_is_in_private_namespace {
-                return Ok(None);
-            }
+        if !private_constants.is_empty()
+            && !private_constants.iter().any(|private_constant| {
+                constant_or_namespace_matches(
+                    private_constant,
+                    &reference.constant_name,
+                )
+            })
+        {
+            return Ok(None);
         }
 
         // START: Original packwerk message
@@ -118,6 +166,7 @@ impl CheckerInterface for Checker {
         Ok(Some(Violation {
             message,
             identifier: pack_checker.violation_identifier(),
+            source_location: reference.source_location.clone(),
         }))
     }
 
@@ -126,6 +175,77 @@ impl CheckerInterface for Checker {
     }
 }
 
+// Entries in private_constants and ignored_private_constants must match at least one
+// constant defined in their pack, so that they do not silently go stale when constants
+// are renamed or moved.
+impl ValidatorInterface for Checker {
+    fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        let has_entries = configuration.pack_set.packs.iter().any(|pack| {
+            !pack.private_constants.is_empty()
+                || !pack.ignored_private_constants.is_empty()
+        });
+        if !has_entries {
+            return None;
+        }
+
+        let constant_resolver = match get_constant_resolver(configuration) {
+            Ok(constant_resolver) => constant_resolver,
+            Err(error) => {
+                return Some(vec![format!(
+                "Could not resolve constants to validate private_constants: {}",
+                error
+            )])
+            }
+        };
+        let mut constants_by_pack_name: HashMap<&str, Vec<&String>> =
+            HashMap::new();
+        for (name, definitions) in constant_resolver
+            .fully_qualified_constant_name_to_constant_definition_map()
+        {
+            for definition in definitions {
+                if let Ok(Some(pack)) = configuration
+                    .pack_set
+                    .for_file(&definition.absolute_path_of_definition)
+                {
+                    constants_by_pack_name
+                        .entry(pack.name.as_str())
+                        .or_default()
+                        .push(name);
+                }
+            }
+        }
+
+        let mut error_messages: Vec<String> = vec![];
+        for pack in &configuration.pack_set.packs {
+            let constants = constants_by_pack_name
+                .get(pack.name.as_str())
+                .cloned()
+                .unwrap_or_default();
+            error_messages.extend(unmatched_entries(
+                pack,
+                "private_constants",
+                &pack.private_constants,
+                &constants,
+                constant_or_namespace_matches,
+            ));
+            error_messages.extend(unmatched_entries(
+                pack,
+                "ignored_private_constants",
+                &pack.ignored_private_constants,
+                &constants,
+                constant_matches,
+            ));
+        }
+
+        if error_messages.is_empty() {
+            None
+        } else {
+            error_messages.sort();
+            Some(error_messages)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -269,6 +389,7 @@ mod tests {
                         .map(|s| s.to_string())
                         .collect(),
                     reason: "deprecated".to_string(),
+                    expires_on: None,
                 }]),
                 ..default_defining_pack()
             }),
@@ -556,4 +677,85 @@ mod tests {
         assert!(result.is_err());
         Ok(())
     }
+
+    #[test]
+    fn test_constant_matches_globs() {
+        assert!(constant_matches(
+            "::Billing::Internal::*",
+            "::Billing::Internal::Ledger"
+        ));
+        assert!(!constant_matches(
+            "::Billing::Internal::*",
+            "::Billing::Internal"
+        ));
+        assert!(!constant_matches(
+            "::Billing::Internal::*",
+            "::Billing::Internal::Ledger::Entry"
+        ));
+        assert!(constant_matches(
+            "::Billing::**::Repository",
+            "::Billing::Repository"
+        ));
+        assert!(constant_matches(
+            "::Billing::**::Repository",
+            "::Billing::Invoices::Drafts::Repository"
+        ));
+        assert!(!constant_matches(
+            "::Billing::**::Repository",
+            "::Billing::Invoices::RepositoryHelper"
+        ));
+        assert!(constant_matches("::Billing::*Job", "::Billing::ChargeJob"));
+        assert!(constant_matches("Billing", "::Billing"));
+    }
+
+    #[test]
+    fn test_constant_or_namespace_matches_nested_constants() {
+        assert!(constant_or_namespace_matches("::Bar", "::Bar::BarChild"));
+        assert!(!constant_or_namespace_matches("::Bar", "::Barn"));
+        assert!(constant_or_namespace_matches(
+            "::Billing::Internal::*",
+            "::Billing::Internal::Ledger::Entry"
+        ));
+        assert!(!constant_or_namespace_matches(
+            "::Billing::Internal::*",
+            "::Billing::Internal"
+        ));
+    }
+
+    #[test]
+    fn test_privacy_constants_glob_includes_referenced_constant(
+    ) -> anyhow::Result<()> {
+        let mut test_checker = TestChecker {
+            reference: Some(Reference {
+                constant_name: String::from("::Bar::Internal::Ledger"),
+                defining_pack_name: Some(String::from("packs/bar")),
+                referencing_pack_name: String::from("packs/foo"),
+                relative_referencing_file: String::from(
+                    "packs/foo/app/services/foo.rb",
+                ),
+                relative_defining_file: Some(String::from(
+                    "packs/bar/app/services/bar/internal/ledger.rb",
+                )),
+                source_location: SourceLocation { line: 3, column: 1 },
+            }),
+            configuration: None,
+            defining_pack: Some(Pack {
+                name: "packs/bar".to_owned(),
+                enforce_privacy: Some(CheckerSetting::True),
+                relative_path: PathBuf::from("packs/bar"),
+                private_constants: HashSet::from([String::from(
+                    "::Bar::Internal::*",
+                )]),
+                ..default_defining_pack()
+            }),
+            referencing_pack: default_referencing_pack(),
+            expected_violation: Some(build_expected_violation_with_constant(
+                String::from("packs/foo/app/services/foo.rb:3:1\nPrivacy violation: `::Bar::Internal::Ledger` is private to `packs/bar`, but referenced from `packs/foo`"),
+                String::from("privacy"), false,
+                String::from("::Bar::Internal::Ledger"),
+            )),
+            ..Default::default()
+        };
+        test_check(&Checker {}, &mut test_checker)
+    }
 }
@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use crate::packs::pack::{CheckerSetting, Pack};
+use crate::packs::{Configuration, PackageTodo};
+
+use super::pack_checker::ViolationType;
+use super::{Violation, ViolationIdentifier};
+
+// A checker setting that was changed from `true` to `strict`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Promotion {
+    pub pack_name: String,
+    pub violation_type: String,
+}
+
+impl fmt::Display for Promotion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match enforcement_key(self.violation_type.as_str().into()) {
+            Some(key) => {
+                write!(f, "Promoted {} in {} to strict", key, self.pack_name)
+            }
+            None => write!(
+                f,
+                "Promoted {} violations in {} to strict",
+                self.violation_type, self.pack_name
+            ),
+        }
+    }
+}
+
+// Architecture rules are not configured per pack, so they have no key in package.yml
+fn enforcement_key(violation_type: ViolationType) -> Option<&'static str> {
+    match violation_type {
+        ViolationType::ArchitectureRule => None,
+        ViolationType::Dependency => Some("enforce_dependencies"),
+        ViolationType::FolderPrivacy => Some("enforce_folder_privacy"),
+        ViolationType::Layer => Some("enforce_layers"),
+        ViolationType::Privacy => Some("enforce_privacy"),
+        ViolationType::Visibility => Some("enforce_visibility"),
+    }
+}
+
+// The pack's setting for the violation type, if it sets one. Architecture rules are not
+// configured per pack, so they can't be promoted.
+fn enforcement_mut(
+    pack: &mut Pack,
+    violation_type: ViolationType,
+) -> Option<&mut CheckerSetting> {
+    match violation_type {
+        ViolationType::ArchitectureRule => None,
+        ViolationType::Dependency => pack.enforce_dependencies.as_mut(),
+        ViolationType::FolderPrivacy => pack
+            .enforce_folder_privacy
+            .as_mut()
+            .map(|folder_privacy| &mut folder_privacy.setting),
+        ViolationType::Layer => pack.enforce_layers.as_mut(),
+        ViolationType::Privacy => pack.enforce_privacy.as_mut(),
+        ViolationType::Visibility => pack.enforce_visibility.as_mut(),
+    }
+}
+
+// The (rules pack, violation type) pairs with at least one violation.
+fn enforced_violations<'a>(
+    identifiers: impl Iterator<Item = &'a ViolationIdentifier>,
+) -> HashSet<(String, String)> {
+    identifiers
+        .filter_map(|identifier| {
+            let violation_type =
+                ViolationType::ALL.into_iter().find(|violation_type| {
+                    <&str>::from(*violation_type) == identifier.violation_type
+                })?;
+            Some((
+                violation_type.rules_pack_name(identifier).to_owned(),
+                identifier.violation_type.clone(),
+            ))
+        })
+        .collect()
+}
+
+// Rewrites each `true` checker setting to `strict` when the pack has no recorded and no
+// live violations of that type, so that it cannot regress. Responsible packs are checked
+// against their updated package_todo.yml in `package_todos_by_pack_name`, all other
+// packs against the one they were loaded with. Promoted packs are added to `updated_packs`,
+// on top of any earlier change to them there, for the caller to write. Returns the promotions.
+pub(crate) fn promote_strict(
+    configuration: &Configuration,
+    package_todos_by_pack_name: &HashMap<String, PackageTodo>,
+    responsible_pack_names: &HashSet<String>,
+    violations: &HashSet<Violation>,
+    updated_packs: &mut BTreeMap<String, Pack>,
+) -> Vec<Promotion> {
+    let empty_package_todo = PackageTodo::default();
+    let recorded: Vec<ViolationIdentifier> = configuration
+        .pack_set
+        .packs
+        .iter()
+        .flat_map(|pack| {
+            let package_todo = if responsible_pack_names.contains(&pack.name) {
+                package_todos_by_pack_name
+                    .get(&pack.name)
+                    .unwrap_or(&empty_package_todo)
+            } else {
+                &pack.package_todo
+            };
+            package_todo.violation_identifiers(&pack.name)
+        })
+        .collect();
+    let enforced = enforced_violations(
+        recorded
+            .iter()
+            .chain(violations.iter().map(|violation| &violation.identifier)),
+    );
+
+    let mut promotions = vec![];
+    for pack in &configuration.pack_set.packs {
+        let mut promoted_pack = updated_packs
+            .get(&pack.name)
+            .cloned()
+            .unwrap_or_else(|| pack.clone());
+        let mut promoted = false;
+        for violation_type in ViolationType::ALL {
+            if violation_type.is_globally_disabled(configuration) {
+                continue;
+            }
+            let Some(setting) =
+                enforcement_mut(&mut promoted_pack, violation_type)
+            else {
+                continue;
+            };
+            if setting != &CheckerSetting::True {
+                continue;
+            }
+            let violation_type_name: &str = violation_type.into();
+            let key = (pack.name.clone(), violation_type_name.to_owned());
+            if enforced.contains(&key) {
+                continue;
+            }
+            *setting = CheckerSetting::Strict;
+            promoted = true;
+            promotions.push(Promotion {
+                pack_name: pack.name.clone(),
+                violation_type: violation_type_name.to_owned(),
+            });
+        }
+        if promoted {
+            updated_packs.insert(pack.name.clone(), promoted_pack);
+        }
+    }
+
+    promotions.sort_by(|a, b| {
+        a.pack_name
+            .cmp(&b.pack_name)
+            .then_with(|| a.violation_type.cmp(&b.violation_type))
+    });
+    promotions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn identifier(
+        violation_type: &str,
+        referencing_pack_name: &str,
+        defining_pack_name: &str,
+    ) -> ViolationIdentifier {
+        ViolationIdentifier {
+            violation_type: violation_type.to_owned(),
+            strict: false,
+            warning: false,
+            file: String::from("packs/foo/app/services/foo.rb"),
+            constant_name: String::from("::Bar"),
+            referencing_pack_name: referencing_pack_name.to_owned(),
+            defining_pack_name: defining_pack_name.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_enforced_violations_use_rules_pack() {
+        let identifiers = [
+            identifier("dependency", "packs/foo", "packs/bar"),
+            identifier("privacy", "packs/foo", "packs/bar"),
+        ];
+        let expected: HashSet<(String, String)> = [
+            (String::from("packs/foo"), String::from("dependency")),
+            (String::from("packs/bar"), String::from("privacy")),
+        ]
+        .into();
+        assert_eq!(enforced_violations(identifiers.iter()), expected);
+    }
+
+    #[test]
+    fn test_promotion_message() {
+        let promotion = Promotion {
+            pack_name: String::from("packs/foo"),
+            violation_type: String::from("folder_privacy"),
+        };
+        assert_eq!(
+            promotion.to_string(),
+            "Promoted enforce_folder_privacy in packs/foo to strict"
+        );
+
+        let promotion = Promotion {
+            violation_type: String::from("architecture_rule"),
+            ..promotion
+        };
+        assert_eq!(
+            promotion.to_string(),
+            "Promoted architecture_rule violations in packs/foo to strict"
+        );
+    }
+}
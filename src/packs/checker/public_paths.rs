@@ -0,0 +1,45 @@
+use crate::packs::ignored;
+use crate::packs::Configuration;
+
+// public_paths patterns that match no files in their pack, e.g. because the files were
+// moved or the pattern has a typo. These are warnings rather than validation errors, since
+// a pack may declare a public path before adding anything to it.
+pub(crate) fn unmatched_public_paths(
+    configuration: &Configuration,
+) -> Vec<String> {
+    let relative_files: Vec<&str> = configuration
+        .included_files
+        .iter()
+        .filter_map(|path| {
+            path.strip_prefix(&configuration.absolute_root)
+                .ok()
+                .and_then(|path| path.to_str())
+        })
+        .collect();
+
+    let mut warnings = vec![];
+    for pack in &configuration.pack_set.packs {
+        let Some(public_paths) = &pack.public_paths else {
+            continue;
+        };
+        let pack_relative_files: Vec<String> = relative_files
+            .iter()
+            .filter_map(|file| pack.pack_relative_file(file))
+            .collect();
+        for public_path in public_paths {
+            let pattern = public_path.strip_prefix('!').unwrap_or(public_path);
+            let matches_a_file = pack_relative_files
+                .iter()
+                .any(|file| ignored::is_match_or_within(pattern, file));
+            if !matches_a_file {
+                warnings.push(format!(
+                    "The public_paths entry `{}` in {} does not match any files",
+                    public_path,
+                    pack.relative_yml().display()
+                ));
+            }
+        }
+    }
+    warnings.sort();
+    warnings
+}
@@ -83,8 +83,13 @@ impl Reference {
             .map(|s| s.as_str())
             .collect::<Vec<&str>>();
 
+        // The experimental resolver returns no definitions, rather than None, for
+        // constants it can't find. Both are kept as a reference without a defining
+        // pack, which checkers of constants that aren't defined in a pack (such as
+        // forbidden constants and gem dependencies) need, and the pack checkers skip.
         let maybe_constant_definition = constant_resolver
-            .resolve(&unresolved_reference.name, &str_namespace_path);
+            .resolve(&unresolved_reference.name, &str_namespace_path)
+            .filter(|constant_definitions| !constant_definitions.is_empty());
 
         if let Some(constant_definitions) = &maybe_constant_definition {
             Ok(constant_definitions
@@ -0,0 +1,126 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::packs::{Configuration, Suppression};
+
+use super::pack_checker::ViolationType;
+use super::Violation;
+
+// A suppression comment for a violation type that has no violation at its location, or
+// one that names no violation types at all (`violation_type` is `None`)
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct UnusedSuppression {
+    pub file: String,
+    pub line: usize,
+    pub violation_type: Option<String>,
+}
+
+impl fmt::Display for UnusedSuppression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.violation_type {
+            Some(violation_type) => write!(
+                f,
+                "{}:{} suppresses {} violations, but there are none",
+                self.file, self.line, violation_type
+            ),
+            None => write!(
+                f,
+                "{}:{} names no violation types, so it suppresses nothing",
+                self.file, self.line
+            ),
+        }
+    }
+}
+
+fn is_globally_disabled(
+    configuration: &Configuration,
+    violation_type: &str,
+) -> bool {
+    ViolationType::ALL.into_iter().any(|known_type| {
+        <&str>::from(known_type) == violation_type
+            && known_type.is_globally_disabled(configuration)
+    })
+}
+
+// Removes the violations that are suppressed by a comment, and returns the suppressions
+// that no longer suppress anything.
+pub(crate) fn apply_suppressions(
+    configuration: &Configuration,
+    violations: HashSet<Violation>,
+    suppressions_by_path: &HashMap<PathBuf, Vec<Suppression>>,
+) -> (HashSet<Violation>, Vec<UnusedSuppression>) {
+    // (relative file, line, violation type) of each suppressed location, mapped to the
+    // line of the comment
+    let mut suppressed: HashMap<(String, usize, String), usize> =
+        HashMap::new();
+    let mut without_types: Vec<UnusedSuppression> = vec![];
+    for (absolute_path, suppressions) in suppressions_by_path {
+        let Some(file) = absolute_path
+            .strip_prefix(&configuration.absolute_root)
+            .ok()
+            .and_then(|path| path.to_str())
+        else {
+            continue;
+        };
+        for suppression in suppressions {
+            if suppression.violation_types.is_empty() {
+                without_types.push(UnusedSuppression {
+                    file: file.to_owned(),
+                    line: suppression.line,
+                    violation_type: None,
+                });
+            }
+            for violation_type in &suppression.violation_types {
+                suppressed.insert(
+                    (
+                        file.to_owned(),
+                        suppression.target_line(),
+                        violation_type.clone(),
+                    ),
+                    suppression.line,
+                );
+            }
+        }
+    }
+
+    let mut used = HashSet::new();
+    let violations = violations
+        .into_iter()
+        .filter(|violation| {
+            let key = (
+                violation.identifier.file.clone(),
+                violation.source_location.line,
+                violation.identifier.violation_type.clone(),
+            );
+            if suppressed.contains_key(&key) {
+                used.insert(key);
+                false
+            } else {
+                true
+            }
+        })
+        .collect();
+
+    let mut unused: Vec<UnusedSuppression> = suppressed
+        .into_iter()
+        .filter(|(key, _)| {
+            !used.contains(key) && !is_globally_disabled(configuration, &key.2)
+        })
+        .map(|((file, _, violation_type), line)| UnusedSuppression {
+            file,
+            line,
+            violation_type: Some(violation_type),
+        })
+        .chain(without_types)
+        .collect();
+    unused.sort_by(|a, b| {
+        a.file
+            .cmp(&b.file)
+            .then_with(|| a.line.cmp(&b.line))
+            .then_with(|| a.violation_type.cmp(&b.violation_type))
+    });
+    (violations, unused)
+}
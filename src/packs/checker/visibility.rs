@@ -1,22 +1,151 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use super::output_helper::print_reference_location;
-use super::pack_checker::PackChecker;
-use super::CheckerInterface;
+use super::pack_checker::{
+    enforced_pack_dependencies, PackChecker, ViolationType,
+};
+use super::{CheckerInterface, ValidatorInterface};
 use crate::packs::checker::Reference;
+use crate::packs::ignored;
+use crate::packs::pack::Pack;
 use crate::packs::{Configuration, Violation};
+use tracing::debug;
 
 pub struct Checker {}
 
-// TODO:
-// Once we implement packs validate, we need to ensure that nothing can add a dependency
-// from a pack to a pack that doesn't permit visibility from the referencing pack
+// An entry in `visible_to`, or in a `pack_visible_to` sigil
+#[derive(Debug, PartialEq, Eq)]
+enum VisibilityRule {
+    // `packs/payments`
+    Pack(String),
+    // `packs/payments/**`
+    Glob(String),
+    // `children`, the packs nested within the defining pack
+    Children,
+    // `owner: Payments`, the packs (or files) with that owner
+    Owner(String),
+}
+
+impl VisibilityRule {
+    fn parse(entry: &str) -> VisibilityRule {
+        let entry = entry.trim();
+        if entry == "children" {
+            VisibilityRule::Children
+        } else if let Some(owner) = entry.strip_prefix("owner:") {
+            VisibilityRule::Owner(owner.trim().to_owned())
+        } else if entry.contains(['*', '?', '[', '{']) {
+            VisibilityRule::Glob(entry.to_owned())
+        } else {
+            VisibilityRule::Pack(entry.to_owned())
+        }
+    }
+
+    fn grants(
+        &self,
+        defining_pack: &Pack,
+        referencing_pack: &Pack,
+        referencing_owner: Option<&str>,
+    ) -> bool {
+        match self {
+            VisibilityRule::Pack(pack_name) => {
+                pack_name == &referencing_pack.name
+            }
+            VisibilityRule::Glob(glob) => {
+                ignored::is_match(glob, &referencing_pack.name)
+            }
+            VisibilityRule::Children => {
+                referencing_pack.name != defining_pack.name
+                    && (defining_pack.name == "."
+                        || referencing_pack
+                            .name
+                            .starts_with(&format!("{}/", defining_pack.name)))
+            }
+            VisibilityRule::Owner(owner) => referencing_owner == Some(owner),
+        }
+    }
+}
+
+impl fmt::Display for VisibilityRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VisibilityRule::Pack(entry) | VisibilityRule::Glob(entry) => {
+                write!(f, "{}", entry)
+            }
+            VisibilityRule::Children => write!(f, "children"),
+            VisibilityRule::Owner(owner) => write!(f, "owner: {}", owner),
+        }
+    }
+}
+
+fn parse_rules(entries: &HashSet<String>) -> Vec<VisibilityRule> {
+    let mut rules: Vec<VisibilityRule> = entries
+        .iter()
+        .map(|entry| VisibilityRule::parse(entry))
+        .collect();
+    rules.sort_by_key(|rule| rule.to_string());
+    rules
+}
+
+fn denial_reason(source: &str, rules: &[VisibilityRule]) -> String {
+    if rules.is_empty() {
+        format!("{} has no visible_to rules", source)
+    } else {
+        format!(
+            "none of the visible_to rules in {} match: {}",
+            source,
+            rules
+                .iter()
+                .map(|rule| format!("`{}`", rule))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+// Declared dependencies must be visible according to the pack's visible_to. File
+// sigils can't be taken into account here, since they apply to single constants.
+impl ValidatorInterface for Checker {
+    fn validate(&self, configuration: &Configuration) -> Option<Vec<String>> {
+        let mut error_messages: Vec<String> = vec![];
+        for pack_dependency in
+            enforced_pack_dependencies(configuration, ViolationType::Visibility)
+        {
+            let (from_pack, to_pack) =
+                (pack_dependency.from_pack, pack_dependency.to_pack);
+            let rules =
+                parse_rules(&to_pack.visible_to.clone().unwrap_or_default());
+            if rules.iter().any(|rule| {
+                rule.grants(to_pack, from_pack, from_pack.owner.as_deref())
+            }) {
+                continue;
+            }
+            error_messages.push(format!(
+                "Invalid 'dependencies' option in '{}'. `{}` is not visible to `{}` ({})",
+                from_pack.relative_yml().to_string_lossy(),
+                to_pack.name,
+                from_pack.name,
+                denial_reason(
+                    &to_pack.relative_yml().display().to_string(),
+                    &rules
+                ),
+            ));
+        }
+
+        if error_messages.is_empty() {
+            None
+        } else {
+            Some(error_messages)
+        }
+    }
+}
+
 impl CheckerInterface for Checker {
     fn check(
         &self,
         reference: &Reference,
         configuration: &Configuration,
-        _sigils: &HashMap<std::path::PathBuf, Vec<crate::packs::Sigil>>,
+        sigils: &HashMap<std::path::PathBuf, Vec<crate::packs::Sigil>>,
     ) -> anyhow::Result<Option<Violation>> {
         let pack_checker =
             PackChecker::new(configuration, reference, &self.violation_type())?;
@@ -24,28 +153,61 @@ impl CheckerInterface for Checker {
             return Ok(None);
         }
         let defining_pack = pack_checker.defining_pack.unwrap();
-        if defining_pack
-            .visible_to
-            .as_ref()
-            .unwrap_or(&HashSet::new())
-            .contains(&pack_checker.referencing_pack.name)
+        let referencing_pack = pack_checker.referencing_pack;
+        // A `pack_visible_to` sigil in the defining file replaces the pack's visible_to
+        let (entries, source): (HashSet<String>, String) = match pack_checker
+            .defining_file_sigil(sigils, "visible_to")
+            .and_then(|value| value.as_list())
         {
+            Some(entries) => (
+                entries.into_iter().collect(),
+                format!(
+                    "the pack_visible_to sigil in {}",
+                    reference.relative_defining_file.as_deref().unwrap_or("")
+                ),
+            ),
+            None => (
+                defining_pack.visible_to.clone().unwrap_or_default(),
+                defining_pack.relative_yml().display().to_string(),
+            ),
+        };
+        let rules = parse_rules(&entries);
+
+        // A `pack_owner` sigil in the referencing file overrides the pack's owner
+        let referencing_owner = pack_checker
+            .referencing_file_sigil(sigils, "owner")
+            .and_then(|value| value.as_str().map(str::to_owned))
+            .or_else(|| referencing_pack.owner.clone());
+        if let Some(rule) = rules.iter().find(|rule| {
+            rule.grants(
+                defining_pack,
+                referencing_pack,
+                referencing_owner.as_deref(),
+            )
+        }) {
+            debug!(
+                "`{}` is visible to `{}` by the visible_to rule `{}` in {}",
+                defining_pack.name, referencing_pack.name, rule, source
+            );
             return Ok(None);
         }
 
         let loc = print_reference_location(reference);
+        let reason = denial_reason(&source, &rules);
 
         let message = format!(
-            "{}Visibility violation: `{}` belongs to `{}`, which is not visible to `{}`",
+            "{}Visibility violation: `{}` belongs to `{}`, which is not visible to `{}` ({})",
             loc,
             reference.constant_name,
             defining_pack.name,
-            pack_checker.referencing_pack.name,
+            referencing_pack.name,
+            reason,
         );
 
         Ok(Some(Violation {
             message,
             identifier: pack_checker.violation_identifier(),
+            source_location: reference.source_location.clone(),
         }))
     }
 
@@ -102,13 +264,14 @@ mod tests {
             defining_pack: Some(Pack {
                 name: "packs/bar".to_owned(),
                 enforce_visibility: Some(CheckerSetting::True),
+                relative_path: PathBuf::from("packs/bar"),
                 ..default_defining_pack()
             }),
             referencing_pack: Pack{
                 relative_path: PathBuf::from("packs/foo"),
                 ..default_referencing_pack()},
             expected_violation: Some(build_expected_violation(
-                "packs/foo/app/services/foo.rb:3:1\nVisibility violation: `::Bar` belongs to `packs/bar`, which is not visible to `packs/foo`".to_string(),
+                "packs/foo/app/services/foo.rb:3:1\nVisibility violation: `::Bar` belongs to `packs/bar`, which is not visible to `packs/foo` (packs/bar/package.yml has no visible_to rules)".to_string(),
                 "visibility".to_string(), false)),
         };
         test_check(&Checker {}, &mut test_checker)
@@ -133,6 +296,7 @@ mod tests {
                         .map(|s| s.to_string())
                         .collect(),
                     reason: "foo is deprecated".to_string(),
+                    expires_on: None,
                 }]),
                 ..default_defining_pack()
             }),
@@ -154,13 +318,14 @@ mod tests {
             defining_pack: Some(Pack {
                 name: "packs/bar".to_owned(),
                 enforce_visibility: Some(CheckerSetting::Strict),
+                relative_path: PathBuf::from("packs/bar"),
                 ..default_defining_pack()
             }),
             referencing_pack: Pack{
                 relative_path: PathBuf::from("packs/foo"),
                 ..default_referencing_pack()},
             expected_violation: Some(build_expected_violation(
-                "packs/foo/app/services/foo.rb:3:1\nVisibility violation: `::Bar` belongs to `packs/bar`, which is not visible to `packs/foo`".to_string(),
+                "packs/foo/app/services/foo.rb:3:1\nVisibility violation: `::Bar` belongs to `packs/bar`, which is not visible to `packs/foo` (packs/bar/package.yml has no visible_to rules)".to_string(),
                 "visibility".to_string(), true)),
         };
         test_check(&Checker {}, &mut test_checker)
@@ -189,4 +354,102 @@ mod tests {
         };
         test_check(&Checker {}, &mut test_checker)
     }
+
+    fn visibility_test_checker(
+        visible_to: &[&str],
+        referencing_pack: Pack,
+        expected_violation: Option<Violation>,
+    ) -> TestChecker {
+        TestChecker {
+            reference: None,
+            configuration: None,
+            referenced_constant_name: Some(String::from("::Bar")),
+            defining_pack: Some(Pack {
+                name: "packs/bar".to_owned(),
+                relative_path: PathBuf::from("packs/bar"),
+                enforce_visibility: Some(CheckerSetting::True),
+                visible_to: Some(
+                    visible_to.iter().map(|entry| entry.to_string()).collect(),
+                ),
+                ..default_defining_pack()
+            }),
+            referencing_pack,
+            expected_violation,
+        }
+    }
+
+    #[test]
+    fn test_visible_to_glob() -> anyhow::Result<()> {
+        let mut test_checker = visibility_test_checker(
+            &["packs/payments/**"],
+            Pack {
+                name: "packs/payments/refunds".to_owned(),
+                relative_path: PathBuf::from("packs/payments/refunds"),
+                ..default_referencing_pack()
+            },
+            None,
+        );
+        test_check(&Checker {}, &mut test_checker)
+    }
+
+    #[test]
+    fn test_visible_to_children() -> anyhow::Result<()> {
+        let mut test_checker = visibility_test_checker(
+            &["children"],
+            Pack {
+                name: "packs/bar/baz".to_owned(),
+                relative_path: PathBuf::from("packs/bar/baz"),
+                ..default_referencing_pack()
+            },
+            None,
+        );
+        test_check(&Checker {}, &mut test_checker)
+    }
+
+    #[test]
+    fn test_visible_to_owner() -> anyhow::Result<()> {
+        let mut test_checker = visibility_test_checker(
+            &["owner: Payments"],
+            Pack {
+                relative_path: PathBuf::from("packs/foo"),
+                owner: Some(String::from("Payments")),
+                ..default_referencing_pack()
+            },
+            None,
+        );
+        test_check(&Checker {}, &mut test_checker)
+    }
+
+    #[test]
+    fn test_visible_to_violation_names_rules() -> anyhow::Result<()> {
+        let mut test_checker = visibility_test_checker(
+            &["children", "owner: Payments", "packs/payments/**"],
+            Pack {
+                relative_path: PathBuf::from("packs/foo"),
+                owner: Some(String::from("Billing")),
+                ..default_referencing_pack()
+            },
+            Some(build_expected_violation(
+                "packs/foo/app/services/foo.rb:3:1\nVisibility violation: `::Bar` belongs to `packs/bar`, which is not visible to `packs/foo` (none of the visible_to rules in packs/bar/package.yml match: `children`, `owner: Payments`, `packs/payments/**`)".to_string(),
+                "visibility".to_string(), false)),
+        );
+        test_check(&Checker {}, &mut test_checker)
+    }
+
+    #[test]
+    fn test_parse_visibility_rules() {
+        assert_eq!(
+            VisibilityRule::parse("packs/foo"),
+            VisibilityRule::Pack(String::from("packs/foo"))
+        );
+        assert_eq!(
+            VisibilityRule::parse("packs/foo/*"),
+            VisibilityRule::Glob(String::from("packs/foo/*"))
+        );
+        assert_eq!(VisibilityRule::parse("children"), VisibilityRule::Children);
+        assert_eq!(
+            VisibilityRule::parse("owner:  Payments"),
+            VisibilityRule::Owner(String::from("Payments"))
+        );
+    }
 }
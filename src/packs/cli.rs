@@ -1,5 +1,6 @@
 use crate::packs;
 
+use crate::packs::configuration::{OutputFormat, TodoStorage};
 use crate::packs::file_utils::get_absolute_path;
 use clap::{Parser, Subcommand};
 use clap_derive::Args;
@@ -54,6 +55,16 @@ struct Args {
     /// Globally disable enforce_visibility
     #[arg(long)]
     disable_enforce_visibility: bool,
+
+    /// An absolute path to the directory containing your gems (for checking gem_dependencies against gem source code)
+    /// Example: /Users/alex.evanczuk/.rbenv/versions/3.2.2/lib/ruby/gems/3.2.0/gems/
+    #[arg(long, requires = "rubydir")]
+    gemdir: Option<PathBuf>,
+
+    /// An absolute path to the directory containing Ruby source code (for telling the Ruby stdlib constants gems reopen apart from the gems' own)
+    /// Example: /Users/alex.evanczuk/.rbenv/versions/3.2.2/lib/ruby/3.2.0/
+    #[arg(long, requires = "gemdir")]
+    rubydir: Option<PathBuf>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -77,6 +88,18 @@ enum Command {
         #[arg(long)]
         ignore_recorded_violations: bool,
 
+        /// Attribute new violations to the commit and author that introduced them (uses `git blame`)
+        #[arg(long)]
+        blame: bool,
+
+        /// Fail when a pack records more violations than its `violation_budget` allows
+        #[arg(long)]
+        ratchet: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+
         files: Vec<String>,
     },
 
@@ -92,7 +115,61 @@ enum Command {
     #[clap(
         about = "Update package_todo.yml files with the current violations"
     )]
-    Update,
+    Update {
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+
+        /// Only remove stale violations, without recording new ones
+        #[arg(long)]
+        prune_only: bool,
+
+        /// Print the changes that would be made to package_todo.yml files without writing them.
+        /// Exits with an error if any violations would be added.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Change `true` checker settings to `strict` in packs left without violations of
+        /// that type
+        #[arg(long)]
+        promote_strict: bool,
+
+        /// Only update the package_todo.yml of this pack (can be repeated)
+        #[arg(long = "pack")]
+        packs: Vec<String>,
+
+        /// Only update violations in these files
+        files: Vec<String>,
+    },
+
+    #[clap(
+        about = "Change `true` checker settings to `strict` in packs without violations of that type"
+    )]
+    PromoteStrict,
+
+    #[clap(
+        about = "Merge conflicting package_todo.yml files (for use as a git merge driver)"
+    )]
+    MergeTodo {
+        /// The common ancestor's version (%O). Its entries that either side
+        /// removed are left out of the merged result.
+        ancestor: PathBuf,
+
+        /// The current version (%A), which is overwritten with the merged result
+        current: PathBuf,
+
+        /// The other branch's version (%B)
+        other: PathBuf,
+    },
+
+    #[clap(
+        about = "Move recorded violations between package_todo.yml files and a single packs_todo.yml"
+    )]
+    ConvertTodoStorage {
+        /// The storage to move recorded violations to
+        #[arg(value_enum)]
+        to: TodoStorage,
+    },
 
     #[clap(about = "Look for validation errors in the codebase")]
     Validate,
@@ -241,6 +318,11 @@ pub fn run() -> anyhow::Result<()> {
         configuration.disable_enforce_visibility = true;
     }
 
+    if args.gemdir.is_some() {
+        configuration.gemdir = args.gemdir;
+        configuration.rubydir = args.rubydir;
+    }
+
     match args.command {
         Command::Greet => {
             packs::greet();
@@ -266,10 +348,16 @@ pub fn run() -> anyhow::Result<()> {
         Command::ListIncludedFiles => packs::list_included_files(configuration),
         Command::Check {
             ignore_recorded_violations,
+            blame,
+            ratchet,
+            format,
             files,
         } => {
             configuration.ignore_recorded_violations =
                 ignore_recorded_violations;
+            configuration.blame_violations = blame;
+            configuration.ratchet = ratchet;
+            configuration.output_format = format;
             configuration.input_files_count = files.len();
             packs::check(&configuration, files)
         }
@@ -285,7 +373,29 @@ pub fn run() -> anyhow::Result<()> {
             configuration.input_files_count = 1;
             packs::check(&configuration, vec![file])
         }
-        Command::Update => packs::update(&configuration),
+        Command::Update {
+            format,
+            prune_only,
+            dry_run,
+            promote_strict,
+            packs: pack_names,
+            files,
+        } => {
+            configuration.output_format = format;
+            configuration.prune_only = prune_only;
+            configuration.dry_run = dry_run;
+            configuration.promote_strict = promote_strict;
+            packs::update(&configuration, pack_names, files)
+        }
+        Command::PromoteStrict => packs::promote_strict(&configuration),
+        Command::MergeTodo {
+            ancestor,
+            current,
+            other,
+        } => packs::merge_todo(&configuration, &ancestor, &current, &other),
+        Command::ConvertTodoStorage { to } => {
+            packs::convert_todo_storage(&configuration, to)
+        }
         Command::Validate => {
             packs::validate(&configuration)
             // Err("💡 Please use `packs check` to detect dependency cycles and run other configuration validations".into())
@@ -2,15 +2,19 @@ use super::caching::{
     cache::Cache, create_cache_dir_idempotently, noop_cache::NoopCache,
     per_file_cache::PerFileCache,
 };
+use super::checker::architecture_rule::ArchitectureRule;
+use super::checker::forbidden_constant::ForbiddenConstant;
 use super::checker::layer::Layers;
+use super::date::Date;
 use super::file_utils::user_inputted_paths_to_absolute_filepaths;
 
 use super::{
-    constant_resolver::ConstantResolverConfiguration, raw_configuration,
-    raw_configuration::RawConfiguration, walk_directory,
+    constant_resolver::ConstantResolverConfiguration, package_todo,
+    raw_configuration, raw_configuration::RawConfiguration, walk_directory,
     walk_directory::WalkDirectoryResult, PackSet,
 };
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::{
     collections::HashSet,
@@ -19,6 +23,38 @@ use std::{
 use tracing::debug;
 use walk_directory::walk_directory;
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Plain text, suitable for a terminal
+    #[default]
+    Text,
+    /// Markdown, suitable for pull request comments
+    Markdown,
+    /// JSON, suitable for other tools
+    Json,
+}
+
+// Where recorded violations are stored
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Deserialize,
+    Serialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStorage {
+    /// A package_todo.yml file in each pack
+    #[default]
+    PerPack,
+    /// A single packs_todo.yml file at the root of the project
+    Central,
+}
+
 pub struct Configuration {
     pub included_files: HashSet<PathBuf>,
     pub input_files_count: usize, // Helpful for optimizations in privacy chcker
@@ -27,6 +63,10 @@ pub struct Configuration {
     pub cache_directory: PathBuf,
     pub pack_set: PackSet,
     pub layers: Layers,
+    pub architecture_rules: Vec<ArchitectureRule>,
+    pub forbidden_constants: Vec<ForbiddenConstant>,
+    pub gemdir: Option<PathBuf>,
+    pub rubydir: Option<PathBuf>,
     pub experimental_parser: bool,
     pub ignored_definitions: HashMap<String, HashSet<PathBuf>>,
     pub autoload_roots: HashMap<PathBuf, String>,
@@ -38,7 +78,17 @@ pub struct Configuration {
     // grows, we can refactor this.
     pub print_files: bool,
     pub packs_first_mode: bool,
+    pub todo_storage: TodoStorage,
+    pub todo_occurrence_counts: bool,
+    pub today: Date,
+    pub expiry_warning_days: u32,
     pub ignore_recorded_violations: bool,
+    pub blame_violations: bool,
+    pub ratchet: bool,
+    pub output_format: OutputFormat,
+    pub prune_only: bool,
+    pub dry_run: bool,
+    pub promote_strict: bool,
     pub disable_enforce_dependencies: bool,
     pub disable_enforce_folder_privacy: bool,
     pub disable_enforce_layers: bool,
@@ -66,8 +116,17 @@ impl Configuration {
     }
 
     pub(crate) fn get_cache(&self) -> Box<dyn Cache + Send + Sync> {
+        self.get_cache_for_parser(self.experimental_parser)
+    }
+
+    // The cache of files processed with the given parser, e.g. for gem sources, which
+    // are always processed with the experimental parser
+    pub(crate) fn get_cache_for_parser(
+        &self,
+        experimental_parser: bool,
+    ) -> Box<dyn Cache + Send + Sync> {
         if self.cache_enabled {
-            let cache_dir = if self.experimental_parser {
+            let cache_dir = if experimental_parser {
                 self.cache_directory.join("experimental")
             } else {
                 self.cache_directory.join("zeitwerk")
@@ -125,15 +184,21 @@ pub(crate) fn from_raw(
     } = walk_directory_result;
 
     let absolute_root = absolute_root.to_path_buf();
+    let todo_storage = raw_config.todo_storage;
+    let included_packs = match todo_storage {
+        TodoStorage::PerPack => included_packs,
+        TodoStorage::Central => package_todo::with_central_package_todos(
+            &absolute_root,
+            included_packs,
+        )?,
+    };
     let pack_set = PackSet::build(included_packs, owning_package_yml_for_file)?;
 
     let cache_directory = absolute_root.join(raw_config.cache_directory);
     let cache_enabled = raw_config.cache;
     let experimental_parser = raw_config.experimental_parser;
 
-    let layers = Layers {
-        layers: raw_config.layers,
-    };
+    let layers = Layers::from(raw_config.layers);
 
     let ignored_definitions = raw_config.ignored_definitions;
     let autoload_roots: HashMap<PathBuf, String> = raw_config.autoload_roots;
@@ -163,6 +228,10 @@ pub(crate) fn from_raw(
         cache_directory,
         pack_set,
         layers,
+        architecture_rules: raw_config.architecture_rules,
+        forbidden_constants: raw_config.forbidden_constants,
+        gemdir: None,
+        rubydir: None,
         experimental_parser,
         ignored_definitions,
         autoload_roots,
@@ -171,7 +240,17 @@ pub(crate) fn from_raw(
         stdin_file_path: None,
         print_files: false,
         packs_first_mode,
+        todo_storage,
+        todo_occurrence_counts: raw_config.todo_occurrence_counts,
+        today: Date::today(),
+        expiry_warning_days: raw_config.expiry_warning_days,
         ignore_recorded_violations: false,
+        blame_violations: false,
+        ratchet: false,
+        output_format: OutputFormat::default(),
+        prune_only: false,
+        dry_run: false,
+        promote_strict: false,
         disable_enforce_dependencies: false,
         disable_enforce_folder_privacy: false,
         disable_enforce_layers: false,
@@ -232,9 +311,15 @@ mod tests {
                 ignored_private_constants: HashSet::new(),
                 private_constants: HashSet::new(),
                 public_folder: None,
+                public_paths: None,
                 layer: None,
                 client_keys: HashMap::new(),
                 enforcement_globs_ignore: None,
+                violation_budget: None,
+                todo_deadline: None,
+                record_incoming_violations: HashSet::new(),
+                gem_dependencies: HashSet::new(),
+                enforce_gem_dependencies: None,
             },
             Pack {
                 enforce_dependencies: None,
@@ -254,9 +339,15 @@ mod tests {
                 ignored_private_constants: HashSet::new(),
                 private_constants: HashSet::new(),
                 public_folder: None,
+                public_paths: None,
                 layer: None,
                 client_keys: HashMap::new(),
                 enforcement_globs_ignore: None,
+                violation_budget: None,
+                todo_deadline: None,
+                record_incoming_violations: HashSet::new(),
+                gem_dependencies: HashSet::new(),
+                enforce_gem_dependencies: None,
             },
             Pack {
                 enforce_dependencies: Some(CheckerSetting::True),
@@ -278,10 +369,16 @@ mod tests {
                 ignored_private_constants: HashSet::new(),
                 private_constants: HashSet::new(),
                 public_folder: None,
+                public_paths: None,
 
                 layer: None,
                 client_keys: HashMap::new(),
                 enforcement_globs_ignore: None,
+                violation_budget: None,
+                todo_deadline: None,
+                record_incoming_violations: HashSet::new(),
+                gem_dependencies: HashSet::new(),
+                enforce_gem_dependencies: None,
             },
             Pack {
                 enforce_dependencies: None,
@@ -301,9 +398,15 @@ mod tests {
                 ignored_private_constants: HashSet::new(),
                 private_constants: HashSet::new(),
                 public_folder: None,
+                public_paths: None,
                 layer: None,
                 client_keys: HashMap::new(),
                 enforcement_globs_ignore: None,
+                violation_budget: None,
+                todo_deadline: None,
+                record_incoming_violations: HashSet::new(),
+                gem_dependencies: HashSet::new(),
+                enforce_gem_dependencies: None,
             },
         ];
 
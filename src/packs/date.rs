@@ -0,0 +1,159 @@
+use std::{
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// A calendar date (UTC), written as `YYYY-MM-DD` in YAML files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    days_since_epoch: i64,
+}
+
+impl Date {
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or_default();
+        Date::from_unix_timestamp(seconds)
+    }
+
+    pub fn from_unix_timestamp(timestamp: i64) -> Date {
+        Date {
+            days_since_epoch: timestamp.div_euclid(86_400),
+        }
+    }
+
+    // Negative when `other` is before `self`
+    pub fn days_until(&self, other: Date) -> i64 {
+        other.days_since_epoch - self.days_since_epoch
+    }
+
+    // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    fn from_civil(year: i64, month: i64, day: i64) -> Date {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4
+            - year_of_era / 100
+            + day_of_year;
+        Date {
+            days_since_epoch: era * 146_097 + day_of_era - 719_468,
+        }
+    }
+
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    fn to_civil(self) -> (i64, i64, i64) {
+        let z = self.days_since_epoch + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1_460
+            + day_of_era / 36_524
+            - day_of_era / 146_096)
+            / 365;
+        let day_of_year = day_of_era
+            - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        (year, month, day)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.to_civil();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || anyhow::anyhow!("Invalid date '{}', expected YYYY-MM-DD", s);
+
+        let mut parts = s.trim().splitn(3, '-');
+        let mut next_number = || -> anyhow::Result<i64> {
+            parts
+                .next()
+                .and_then(|part| part.parse::<i64>().ok())
+                .ok_or_else(invalid)
+        };
+        let (year, month, day) =
+            (next_number()?, next_number()?, next_number()?);
+
+        let date = Date::from_civil(year, month, day);
+        // Dates such as 2023-02-30 do not survive the round trip
+        if date.to_civil() != (year, month, day) {
+            return Err(invalid());
+        }
+        Ok(date)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_unix_timestamp() {
+        assert_eq!(Date::from_unix_timestamp(0).to_string(), "1970-01-01");
+        assert_eq!(
+            Date::from_unix_timestamp(951_782_400).to_string(),
+            "2000-02-29"
+        );
+        assert_eq!(
+            Date::from_unix_timestamp(1_709_251_199).to_string(),
+            "2024-02-29"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for date in ["1970-01-01", "2000-02-29", "2024-12-31", "2999-01-15"] {
+            assert_eq!(date.parse::<Date>().unwrap().to_string(), date);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for date in ["2023-02-30", "2023-13-01", "tomorrow", "2023-01"] {
+            assert!(date.parse::<Date>().is_err(), "{} should not parse", date);
+        }
+    }
+
+    #[test]
+    fn test_days_until() {
+        let start: Date = "2024-02-27".parse().unwrap();
+        let end: Date = "2024-03-02".parse().unwrap();
+        assert_eq!(start.days_until(end), 4);
+        assert_eq!(end.days_until(start), -4);
+    }
+}
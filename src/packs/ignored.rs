@@ -15,7 +15,13 @@ pub fn is_ignored(rules: &HashSet<String>, path: &str) -> anyhow::Result<bool> {
     Ok(false)
 }
 
-fn is_match(rule: &str, path: &str) -> bool {
+// Whether `rule` matches `path` itself or one of its parent directories
+pub fn is_match_or_within(rule: &str, path: &str) -> bool {
+    is_match(rule, path)
+        || is_match(&format!("{}/**", rule.trim_end_matches('/')), path)
+}
+
+pub fn is_match(rule: &str, path: &str) -> bool {
     match fnmatch_regex2::glob_to_regex(rule) {
         Ok(regex) => regex.is_match(path),
         _ => false,
@@ -80,4 +86,17 @@ mod tests {
         assert!(is_match("packs/foo/**", "packs/foo/app/services/my.rb"));
         assert!(is_match("packs/foo/**/*", "packs/foo/app/services/my.rb"));
     }
+
+    #[test]
+    fn test_is_match_or_within() {
+        assert!(is_match_or_within("app/public", "app/public/foo.rb"));
+        assert!(is_match_or_within("app/public/", "app/public/foo/bar.rb"));
+        assert!(is_match_or_within(
+            "app/*/public",
+            "app/graphql/public/a.rb"
+        ));
+        assert!(is_match_or_within("app/public/foo.rb", "app/public/foo.rb"));
+        assert!(!is_match_or_within("app/public", "app/public_api/foo.rb"));
+        assert!(!is_match_or_within("app/public", "lib/app/public/foo.rb"));
+    }
 }
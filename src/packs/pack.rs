@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fs::File,
     hash::Hasher,
     io::Read,
@@ -12,7 +12,8 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_yaml::Value;
 
 use super::{
-    checker::ViolationIdentifier, file_utils::expand_glob, ignored, PackageTodo,
+    checker::ViolationIdentifier, date::Date, file_utils::expand_glob, ignored,
+    package_todo, PackageTodo,
 };
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
@@ -98,17 +99,18 @@ pub struct Pack {
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
-        serialize_with = "serialize_sorted_option_hashset_of_strings"
+        serialize_with = "serialize_sorted_option_hashset_of_strings",
+        deserialize_with = "deserialize_visible_to"
     )]
     pub visible_to: Option<HashSet<String>>,
 
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
-        serialize_with = "serialize_checker_setting",
-        deserialize_with = "deserialize_checker_setting"
+        serialize_with = "serialize_folder_privacy_setting",
+        deserialize_with = "deserialize_folder_privacy_setting"
     )]
-    pub enforce_folder_privacy: Option<CheckerSetting>,
+    pub enforce_folder_privacy: Option<FolderPrivacySetting>,
 
     #[serde(
         default,
@@ -121,11 +123,49 @@ pub struct Pack {
     #[serde(skip_serializing_if = "is_default_public_folder")]
     pub public_folder: Option<PathBuf>,
 
+    // Globs relative to the pack, e.g. `app/public` or `lib/**/api`. A pattern matches the
+    // files it names and everything within them. Patterns starting with `!` exclude files.
+    // Takes the place of public_folder when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_paths: Option<Vec<String>>,
+
     #[serde(flatten)]
     pub client_keys: HashMap<String, Value>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enforcement_globs_ignore: Option<Vec<EnforcementGlobsIgnore>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub violation_budget: Option<ViolationBudget>,
+
+    // Recorded violations are reported as new violations after this date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub todo_deadline: Option<Date>,
+
+    // Violation types (e.g. privacy) that are recorded in this pack's package_todo.yml
+    // when other packs reference its constants, rather than in the referencing pack's
+    #[serde(
+        default,
+        skip_serializing_if = "HashSet::is_empty",
+        serialize_with = "serialize_sorted_hashset_of_strings"
+    )]
+    pub record_incoming_violations: HashSet<String>,
+
+    // Gems from the Gemfile.lock whose constants this pack may reference
+    #[serde(
+        default,
+        skip_serializing_if = "HashSet::is_empty",
+        serialize_with = "serialize_sorted_hashset_of_strings"
+    )]
+    pub gem_dependencies: HashSet<String>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_checker_setting",
+        deserialize_with = "deserialize_checker_setting"
+    )]
+    pub enforce_gem_dependencies: Option<CheckerSetting>,
 }
 
 impl Hash for Pack {
@@ -152,6 +192,71 @@ pub struct EnforcementGlobsIgnore {
 
     #[serde(default)]
     pub reason: String,
+
+    // The ignore no longer applies after this date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_on: Option<Date>,
+}
+
+impl EnforcementGlobsIgnore {
+    pub fn is_expired(&self, today: Date) -> bool {
+        self.expires_on.is_some_and(|expires_on| expires_on < today)
+    }
+}
+
+// The maximum number of violations a pack may record in its package_todo.yml,
+// either in total or per violation type.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum ViolationBudget {
+    Total(usize),
+    ByViolationType(BTreeMap<String, usize>),
+}
+
+// `default` lets the root pack, sibling packs and ancestor packs see a pack. The other
+// policies still let the root pack see everything.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum FolderPrivacyPolicy {
+    Named(FolderPrivacyPolicyName),
+    // Ancestor packs that are this many folders above the pack, e.g. 1 for the parent
+    AncestorDepths { ancestor_depths: Vec<usize> },
+}
+
+impl Default for FolderPrivacyPolicy {
+    fn default() -> Self {
+        Self::Named(FolderPrivacyPolicyName::Default)
+    }
+}
+
+// `enforce_folder_privacy` takes a checker setting, a policy (which enforces it as `true`),
+// or both as `{ setting: strict, policy: siblings }`
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct FolderPrivacySetting {
+    pub setting: CheckerSetting,
+    pub policy: FolderPrivacyPolicy,
+}
+
+impl From<CheckerSetting> for FolderPrivacySetting {
+    fn from(setting: CheckerSetting) -> Self {
+        Self {
+            setting,
+            policy: FolderPrivacyPolicy::default(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderPrivacyPolicyName {
+    Default,
+    // Only packs in the same folder
+    Siblings,
+    // Only the pack in the folder directly above
+    Parent,
+    // Only packs in the same top-level domain folder, e.g. `packs/payments` for
+    // `packs/payments/refunds`
+    SameDomain,
 }
 
 #[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize, Clone)]
@@ -160,6 +265,9 @@ pub enum CheckerSetting {
     False,
     True,
     Strict,
+    // Strict, except in files already recorded in the pack's package_todo.yml.
+    StrictForNewFiles,
+    Warn,
 }
 
 impl CheckerSetting {
@@ -170,6 +278,14 @@ impl CheckerSetting {
     pub fn is_strict(&self) -> bool {
         matches!(self, Self::Strict)
     }
+
+    pub fn is_strict_for_new_files(&self) -> bool {
+        matches!(self, Self::StrictForNewFiles)
+    }
+
+    pub fn is_warn(&self) -> bool {
+        matches!(self, Self::Warn)
+    }
 }
 
 impl Pack {
@@ -178,27 +294,7 @@ impl Pack {
     }
 
     pub fn all_violations(&self) -> Vec<ViolationIdentifier> {
-        let mut violations = Vec::new();
-        let violations_by_pack = &self.package_todo.violations_by_defining_pack;
-        for (defining_pack_name, violation_groups) in violations_by_pack {
-            for (constant_name, violation_group) in violation_groups {
-                for violation_type in &violation_group.violation_types {
-                    for file in &violation_group.files {
-                        let identifier = ViolationIdentifier {
-                            violation_type: violation_type.clone(),
-                            strict: false,
-                            file: file.clone(),
-                            constant_name: constant_name.clone(),
-                            referencing_pack_name: self.name.clone(),
-                            defining_pack_name: defining_pack_name.clone(),
-                        };
-
-                        violations.push(identifier);
-                    }
-                }
-            }
-        }
-        violations
+        self.package_todo.violation_identifiers(&self.name)
     }
 
     pub fn from_path(
@@ -226,23 +322,9 @@ impl Pack {
             .unwrap()
             .join("package_todo.yml");
 
-        let package_todo: PackageTodo = if absolute_path_to_package_todo
-            .exists()
-        {
-            let mut package_todo_contents = String::new();
-            let mut file = File::open(&absolute_path_to_package_todo)
-                .context("Failed to open the package_todo.yml file")?;
-            file.read_to_string(&mut package_todo_contents)
-                .context("Could not read the package_todo.yml file")?;
-            serde_yaml::from_str(&package_todo_contents).with_context(|| {
-                format!(
-                    "Failed to deserialize the package_todo.yml file at {}. Try deleting the file and running the `update` command to regenerate it.",
-                    absolute_path_to_package_todo.display()
-                )
-            })?
-        } else {
-            PackageTodo::default()
-        };
+        let package_todo = package_todo::read_package_todo_file(
+            &absolute_path_to_package_todo,
+        )?;
 
         Pack::from_contents(
             package_yml_absolute_path,
@@ -315,20 +397,24 @@ impl Pack {
     }
 
     pub(crate) fn enforce_folder_privacy(&self) -> &CheckerSetting {
-        if self.enforce_folder_privacy.is_none() {
+        match &self.enforce_folder_privacy {
+            Some(folder_privacy) => &folder_privacy.setting,
             // enforce_folder_visibility is deprecated
-            match &self.enforce_folder_visibility {
-                Some(setting) => setting,
-                None => &CheckerSetting::False,
-            }
-        } else {
-            match &self.enforce_folder_privacy {
+            None => match &self.enforce_folder_visibility {
                 Some(setting) => setting,
                 None => &CheckerSetting::False,
-            }
+            },
         }
     }
 
+    // Which packs can see this pack when enforce_folder_privacy is set
+    pub(crate) fn folder_privacy_policy(&self) -> FolderPrivacyPolicy {
+        self.enforce_folder_privacy
+            .as_ref()
+            .map(|folder_privacy| folder_privacy.policy.clone())
+            .unwrap_or_default()
+    }
+
     pub(crate) fn public_folder(&self) -> PathBuf {
         match &self.public_folder {
             Some(folder) => folder.to_owned(),
@@ -336,19 +422,56 @@ impl Pack {
         }
     }
 
+    pub(crate) fn is_public_file(&self, relative_file: &str) -> bool {
+        let Some(public_paths) = &self.public_paths else {
+            return relative_file
+                .starts_with(self.public_folder().to_string_lossy().as_ref());
+        };
+        let Some(pack_relative_file) = self.pack_relative_file(relative_file)
+        else {
+            return false;
+        };
+        let (excluded, included): (Vec<&String>, Vec<&String>) =
+            public_paths.iter().partition(|path| path.starts_with('!'));
+        included
+            .iter()
+            .any(|path| ignored::is_match_or_within(path, &pack_relative_file))
+            && !excluded.iter().any(|path| {
+                ignored::is_match_or_within(&path[1..], &pack_relative_file)
+            })
+    }
+
+    // A path relative to the project root, relative to the pack instead
+    pub(crate) fn pack_relative_file(
+        &self,
+        relative_file: &str,
+    ) -> Option<String> {
+        if self.name == "." {
+            return Some(relative_file.to_owned());
+        }
+        Path::new(relative_file)
+            .strip_prefix(&self.relative_path)
+            .ok()
+            .and_then(|path| path.to_str())
+            .map(String::from)
+    }
+
     pub(crate) fn add_dependency(&self, to_pack: &Pack) -> Pack {
         let mut new_pack = self.clone();
         new_pack.dependencies.insert(to_pack.name.clone());
         new_pack
     }
 
+    // The first enforcement_globs_ignore entry for `enforcement` that has not expired
     pub(crate) fn ignores_for_enforcement(
         &self,
         enforcement: &str,
+        today: Date,
     ) -> Option<&HashSet<String>> {
         self.enforcement_globs_ignore.as_ref().and_then(|ignores| {
             ignores
                 .iter()
+                .filter(|ignore| !ignore.is_expired(today))
                 .find(|ignore| ignore.enforcements.contains(enforcement))
                 .map(|ignore| &ignore.ignores)
         })
@@ -358,12 +481,19 @@ impl Pack {
         &self,
         file_path: &str,
         enforcement: &str,
+        today: Date,
     ) -> anyhow::Result<bool> {
-        if let Some(ignore_rules) = self.ignores_for_enforcement(enforcement) {
+        if let Some(ignore_rules) =
+            self.ignores_for_enforcement(enforcement, today)
+        {
             return ignored::is_ignored(ignore_rules, file_path);
         }
         Ok(false)
     }
+
+    pub(crate) fn is_todo_expired(&self, today: Date) -> bool {
+        self.todo_deadline.is_some_and(|deadline| deadline < today)
+    }
 }
 
 fn serialize_sorted_hashset_of_strings<S>(
@@ -407,13 +537,19 @@ const KEY_SORT_ORDER: &[&str] = &[
     "enforce_folder_privacy",
     "enforce_folder_visibility",
     "enforce_architecture",
+    "enforce_gem_dependencies",
     "layer",
     "public_path",
+    "public_paths",
     "dependencies",
+    "gem_dependencies",
     "owner",
     "private_constants",
     "visible_to",
     "enforcement_globs_ignore",
+    "violation_budget",
+    "todo_deadline",
+    "record_incoming_violations",
     "metadata",
 ];
 
@@ -487,7 +623,7 @@ pub fn write_pack_to_disk(pack: &Pack) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn serialize_checker_setting<S>(
+pub(crate) fn serialize_checker_setting<S>(
     value: &Option<CheckerSetting>,
     serializer: S,
 ) -> Result<S::Ok, S::Error>
@@ -498,11 +634,26 @@ where
         Some(CheckerSetting::False) => serializer.serialize_bool(false),
         Some(CheckerSetting::True) => serializer.serialize_bool(true),
         Some(CheckerSetting::Strict) => serializer.serialize_str("strict"),
+        Some(CheckerSetting::StrictForNewFiles) => {
+            serializer.serialize_str("strict_for_new_files")
+        }
+        Some(CheckerSetting::Warn) => serializer.serialize_str("warn"),
         None => serializer.serialize_none(),
     }
 }
 
-fn deserialize_checker_setting<'de, D>(
+fn parse_checker_setting(s: &str) -> Option<CheckerSetting> {
+    match s {
+        "false" => Some(CheckerSetting::False),
+        "true" => Some(CheckerSetting::True),
+        "strict" => Some(CheckerSetting::Strict),
+        "strict_for_new_files" => Some(CheckerSetting::StrictForNewFiles),
+        "warn" => Some(CheckerSetting::Warn),
+        _ => None,
+    }
+}
+
+pub(crate) fn deserialize_checker_setting<'de, D>(
     deserializer: D,
 ) -> Result<Option<CheckerSetting>, D::Error>
 where
@@ -511,16 +662,131 @@ where
     // Deserialize an optional String
     let s = String::deserialize(deserializer);
 
-    match s.unwrap().as_str() {
-        "false" => Ok(Some(CheckerSetting::False)),
-        "true" => Ok(Some(CheckerSetting::True)),
-        "strict" => Ok(Some(CheckerSetting::Strict)),
-        _ => Err(serde::de::Error::custom(
-            "expected one of: false, true, strict",
+    match parse_checker_setting(s.unwrap().as_str()) {
+        Some(setting) => Ok(Some(setting)),
+        None => Err(serde::de::Error::custom(
+            "expected one of: false, true, strict, strict_for_new_files, warn",
+        )),
+    }
+}
+
+// The `{ setting: strict, policy: siblings }` form of `enforce_folder_privacy`
+#[derive(Serialize)]
+struct FolderPrivacySettingWithPolicy {
+    #[serde(serialize_with = "serialize_checker_setting")]
+    setting: Option<CheckerSetting>,
+    policy: FolderPrivacyPolicy,
+}
+
+fn serialize_folder_privacy_setting<S>(
+    value: &Option<FolderPrivacySetting>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let Some(folder_privacy) = value else {
+        return serializer.serialize_none();
+    };
+    // The shortest form that reads back the same
+    match (&folder_privacy.setting, &folder_privacy.policy) {
+        (
+            setting,
+            FolderPrivacyPolicy::Named(FolderPrivacyPolicyName::Default),
+        ) => serialize_checker_setting(&Some(setting.clone()), serializer),
+        (CheckerSetting::True, policy) => policy.serialize(serializer),
+        (setting, policy) => FolderPrivacySettingWithPolicy {
+            setting: Some(setting.clone()),
+            policy: policy.clone(),
+        }
+        .serialize(serializer),
+    }
+}
+
+fn checker_setting_from_value(value: &Value) -> Option<CheckerSetting> {
+    match value {
+        Value::Bool(true) => Some(CheckerSetting::True),
+        Value::Bool(false) => Some(CheckerSetting::False),
+        Value::String(s) => parse_checker_setting(s),
+        _ => None,
+    }
+}
+
+fn deserialize_folder_privacy_setting<'de, D>(
+    deserializer: D,
+) -> Result<Option<FolderPrivacySetting>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Value::deserialize(deserializer)?;
+    if let Some(setting) = checker_setting_from_value(&value) {
+        return Ok(Some(setting.into()));
+    }
+
+    let policy_from_value = |value: &Value| {
+        serde_yaml::from_value::<FolderPrivacyPolicy>(value.clone()).ok()
+    };
+    let folder_privacy = match &value {
+        Value::Mapping(mapping) if mapping.contains_key("policy") => {
+            let only_known_keys = mapping.keys().all(|key| {
+                key.as_str()
+                    .is_some_and(|key| key == "setting" || key == "policy")
+            });
+            let setting = match mapping.get("setting") {
+                Some(setting) => checker_setting_from_value(setting),
+                None => Some(CheckerSetting::True),
+            };
+            let policy = mapping.get("policy").and_then(policy_from_value);
+            setting.zip(policy).filter(|_| only_known_keys)
+        }
+        _ => policy_from_value(&value)
+            .map(|policy| (CheckerSetting::True, policy)),
+    };
+    match folder_privacy {
+        Some((setting, policy)) => {
+            Ok(Some(FolderPrivacySetting { setting, policy }))
+        }
+        None => Err(serde::de::Error::custom(
+            "expected enforce_folder_privacy to be one of: false, true, strict, strict_for_new_files, warn, a folder privacy policy (default, siblings, parent, same_domain or `ancestor_depths: [...]`), or `{ setting: ..., policy: ... }`",
         )),
     }
 }
 
+// `visible_to` entries are strings, except `owner: Team`, which YAML parses as a map
+// unless it's quoted. Both forms are read as the string `owner: Team`.
+fn deserialize_visible_to<'de, D>(
+    deserializer: D,
+) -> Result<Option<HashSet<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(entries) = Option::<Vec<Value>>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    entries
+        .into_iter()
+        .map(|entry| match entry {
+            Value::String(entry) => Ok(entry),
+            Value::Mapping(mapping) if mapping.len() == 1 => {
+                match mapping.get("owner") {
+                    Some(Value::String(owner)) => {
+                        Ok(format!("owner: {}", owner))
+                    }
+                    _ => Err(invalid_visible_to_entry()),
+                }
+            }
+            _ => Err(invalid_visible_to_entry()),
+        })
+        .collect::<Result<HashSet<String>, D::Error>>()
+        .map(Some)
+}
+
+fn invalid_visible_to_entry<E: serde::de::Error>() -> E {
+    E::custom(
+        "expected visible_to entries to be pack names, globs, `children` or `owner: <Team>`",
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test_util;
@@ -562,6 +828,8 @@ dependencies:
 # some comment
 enforce_privacy: true
 enforce_dependencies: strict
+enforce_visibility: warn
+enforce_folder_privacy: strict_for_new_files
 dependencies:
   - packs/c
   - packs/a
@@ -574,6 +842,8 @@ foobar: true
         let expected = r#"
 enforce_dependencies: strict
 enforce_privacy: true
+enforce_visibility: warn
+enforce_folder_privacy: strict_for_new_files
 dependencies:
 - packs/a
 - packs/b
@@ -655,6 +925,41 @@ visible_to:
         assert_eq!(expected, actual)
     }
 
+    #[test]
+    fn test_serde_with_visible_to_owner() {
+        let pack_yml = r#"
+visible_to:
+  - packs/a
+  - owner: Payments
+  - "owner: Finance"
+"#;
+
+        let actual = reserialize_pack(pack_yml);
+
+        let expected = r#"
+visible_to:
+- 'owner: Finance'
+- 'owner: Payments'
+- packs/a
+"#
+        .trim_start();
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn test_deserialize_invalid_visible_to_entry() {
+        let pack_yml = r#"
+visible_to:
+  - team: Payments
+"#;
+
+        let error = serde_yaml::from_str::<Pack>(pack_yml).unwrap_err();
+        assert!(error.to_string().contains(
+            "expected visible_to entries to be pack names, globs, `children` or `owner: <Team>`"
+        ));
+    }
+
     #[test]
     fn test_serde_with_metadata() {
         let pack_yml = r#"
@@ -699,6 +1004,31 @@ dependencies:
         assert_eq!(expected, actual)
     }
 
+    #[test]
+    fn test_serde_with_violation_budget() {
+        let pack_yml = r#"
+violation_budget:
+  privacy: 3
+  dependency: 5
+enforce_dependencies: true
+"#;
+
+        let actual = reserialize_pack(pack_yml);
+
+        let expected = r#"
+enforce_dependencies: true
+violation_budget:
+  dependency: 5
+  privacy: 3
+"#
+        .trim_start();
+
+        assert_eq!(expected, actual);
+
+        let pack: Pack = serde_yaml::from_str("violation_budget: 10").unwrap();
+        assert_eq!(pack.violation_budget, Some(ViolationBudget::Total(10)));
+    }
+
     #[test]
     fn test_serde_with_owner() {
         let pack_yml = r#"
@@ -717,6 +1047,61 @@ owner: Foobar
         assert_eq!(expected, actual)
     }
 
+    #[test]
+    fn test_serde_with_folder_privacy_policy() {
+        for pack_yml in [
+            "enforce_folder_privacy: same_domain\n",
+            "enforce_folder_privacy:\n  ancestor_depths:\n  - 1\n  - 2\n",
+            "enforce_folder_privacy:\n  setting: strict\n  policy: siblings\n",
+            "enforce_folder_privacy:\n  setting: warn\n  policy:\n    ancestor_depths:\n    - 1\n",
+        ] {
+            assert_eq!(pack_yml, reserialize_pack(pack_yml));
+        }
+
+        let pack: Pack =
+            serde_yaml::from_str("enforce_folder_privacy: parent").unwrap();
+        assert_eq!(
+            pack.enforce_folder_privacy,
+            Some(FolderPrivacySetting {
+                setting: CheckerSetting::True,
+                policy: FolderPrivacyPolicy::Named(
+                    FolderPrivacyPolicyName::Parent
+                ),
+            })
+        );
+
+        // A policy given with `true`, or the default policy, is written in its short form
+        assert_eq!(
+            "enforce_folder_privacy: parent\n",
+            reserialize_pack(
+                "enforce_folder_privacy:\n  setting: true\n  policy: parent\n"
+            )
+        );
+        assert_eq!(
+            "enforce_folder_privacy: strict\n",
+            reserialize_pack(
+                "enforce_folder_privacy:\n  setting: strict\n  policy: default\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid_folder_privacy_setting() {
+        for pack_yml in [
+            "enforce_folder_privacy: cousins",
+            "enforce_folder_privacy:\n  setting: strict\n  policy: cousins",
+        ] {
+            let error = serde_yaml::from_str::<Pack>(pack_yml).unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .contains("expected enforce_folder_privacy to be one of"),
+                "{}",
+                error
+            );
+        }
+    }
+
     #[test]
     fn test_serde_with_enforcement_globs() {
         let pack_yml = r#"
@@ -750,6 +1135,7 @@ enforcement_globs_ignore:
                         .map(|s| s.to_string())
                         .collect(),
                     reason: "deprecated foo".to_string(),
+                    expires_on: None,
                 },
                 EnforcementGlobsIgnore {
                     enforcements: ["layer"]
@@ -761,6 +1147,7 @@ enforcement_globs_ignore:
                         .map(|s| s.to_string())
                         .collect(),
                     reason: "deprecated bar".to_string(),
+                    expires_on: None,
                 },
             ]
         );
@@ -771,7 +1158,7 @@ enforcement_globs_ignore:
         assert_eq!(pack, re_pack);
 
         assert_eq!(
-            pack.ignores_for_enforcement("privacy"),
+            pack.ignores_for_enforcement("privacy", Date::today()),
             Some(&{
                 ["**/*", "!packs/foo"]
                     .iter()
@@ -779,7 +1166,64 @@ enforcement_globs_ignore:
                     .collect()
             })
         );
-        assert_eq!(pack.ignores_for_enforcement("nope"), None);
+        assert_eq!(pack.ignores_for_enforcement("nope", Date::today()), None);
+    }
+
+    #[test]
+    fn test_expired_enforcement_globs_ignore() {
+        let pack_yml = r#"
+todo_deadline: 2024-03-31
+enforcement_globs_ignore:
+  - enforcements:
+      - privacy
+    ignores:
+      - packs/bar/**
+    expires_on: 2024-03-10
+        "#
+        .trim_start();
+
+        let pack: Pack = serde_yaml::from_str(pack_yml).unwrap();
+        let date = |s: &str| s.parse::<Date>().unwrap();
+
+        assert!(pack
+            .ignores_for_enforcement("privacy", date("2024-03-10"))
+            .is_some());
+        assert_eq!(
+            pack.ignores_for_enforcement("privacy", date("2024-03-11")),
+            None
+        );
+        assert!(!pack.is_todo_expired(date("2024-03-31")));
+        assert!(pack.is_todo_expired(date("2024-04-01")));
+    }
+
+    #[test]
+    fn test_is_public_file() {
+        let pack_yml = r#"
+public_paths:
+  - app/public
+  - lib/**/api
+  - "!app/public/internal"
+        "#
+        .trim_start();
+
+        let pack = Pack {
+            name: String::from("packs/foo"),
+            relative_path: PathBuf::from("packs/foo"),
+            ..serde_yaml::from_str(pack_yml).unwrap()
+        };
+
+        assert!(pack.is_public_file("packs/foo/app/public/foo.rb"));
+        assert!(pack.is_public_file("packs/foo/lib/foo/api/client.rb"));
+        assert!(!pack.is_public_file("packs/foo/app/public/internal/foo.rb"));
+        assert!(!pack.is_public_file("packs/foo/app/services/foo.rb"));
+        assert!(!pack.is_public_file("packs/bar/app/public/foo.rb"));
+
+        let pack_without_public_paths = Pack {
+            public_paths: None,
+            ..pack
+        };
+        assert!(pack_without_public_paths
+            .is_public_file("packs/foo/app/public/internal/foo.rb"));
     }
 
     #[test]
@@ -823,6 +1267,7 @@ enforcement_globs_ignore:
             ViolationIdentifier {
                 violation_type: "dependency".to_string(),
                 strict: false,
+                warning: false,
                 file: "packs/foo/app/services/foo.rb".to_string(),
                 constant_name: "::Bar".to_string(),
                 referencing_pack_name: "packs/foo".to_string(),
@@ -831,6 +1276,7 @@ enforcement_globs_ignore:
             ViolationIdentifier {
                 violation_type: "dependency".to_string(),
                 strict: false,
+                warning: false,
                 file: "packs/foo/app/services/other_foo.rb".to_string(),
                 constant_name: "::Bar".to_string(),
                 referencing_pack_name: "packs/foo".to_string(),
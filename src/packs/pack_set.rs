@@ -20,6 +20,8 @@ pub struct PackSet {
     // We will also likely want to have an optimization that only rewrites the files
     // that have different violations.
     pub all_violations: HashSet<ViolationIdentifier>,
+    // Recorded violations that only cover a number of references, see `ViolationGroup::occurrences`
+    pub recorded_occurrences: HashMap<ViolationIdentifier, usize>,
 }
 
 #[derive(Debug)]
@@ -45,12 +47,13 @@ impl PackSet {
         let mut indexed_packs_by_yml: HashMap<PathBuf, String> = HashMap::new();
 
         let mut all_violations = HashSet::new();
+        let mut recorded_occurrences = HashMap::new();
         for pack in &packs {
             indexed_packs_by_name.insert(pack.name.clone(), pack.clone());
             indexed_packs_by_yml.insert(pack.yml.clone(), pack.name.clone());
-            for violation_identifier in pack.all_violations() {
-                all_violations.insert(violation_identifier);
-            }
+            all_violations.extend(pack.all_violations());
+            recorded_occurrences
+                .extend(pack.package_todo.occurrence_limits(&pack.name));
         }
 
         let mut owning_pack_name_for_file: HashMap<PathBuf, String> =
@@ -72,10 +75,30 @@ impl PackSet {
             indexed_packs,
             packs,
             all_violations,
+            recorded_occurrences,
             owning_pack_name_for_file,
         })
     }
 
+    // Violations are recorded in the referencing pack's package_todo.yml, unless the
+    // defining pack records incoming violations of that type
+    pub fn responsible_pack_name<'a>(
+        &self,
+        violation_identifier: &'a ViolationIdentifier,
+    ) -> &'a str {
+        let records_incoming_violations = self
+            .for_pack(&violation_identifier.defining_pack_name)
+            .is_ok_and(|pack| {
+                pack.record_incoming_violations
+                    .contains(&violation_identifier.violation_type)
+            });
+        if records_incoming_violations {
+            &violation_identifier.defining_pack_name
+        } else {
+            &violation_identifier.referencing_pack_name
+        }
+    }
+
     pub fn for_file(
         &self,
         absolute_file_path: &Path,
@@ -132,7 +155,7 @@ impl PackSet {
 mod tests {
     use std::collections::{HashMap, HashSet};
 
-    use crate::packs::pack::Pack;
+    use crate::packs::{checker::ViolationIdentifier, pack::Pack};
 
     use super::PackSet;
 
@@ -164,4 +187,48 @@ mod tests {
         let actual_pack = pack_set.for_pack("packs/foo/");
         assert!(actual_pack.is_ok());
     }
+
+    #[test]
+    fn test_incoming_violations() {
+        let bar_pack = Pack {
+            name: "packs/bar".to_string(),
+            record_incoming_violations: ["privacy".to_string()].into(),
+            ..Pack::default()
+        };
+        let foo_pack = Pack {
+            name: "packs/foo".to_string(),
+            ..Pack::default()
+        };
+        let root_pack = Pack {
+            name: ".".to_string(),
+            ..Pack::default()
+        };
+        let pack_set = PackSet::build(
+            [bar_pack, foo_pack, root_pack].into(),
+            HashMap::new(),
+        )
+        .unwrap();
+
+        let identifier = |violation_type: &str, referencing_pack_name: &str| {
+            ViolationIdentifier {
+                violation_type: violation_type.to_string(),
+                strict: false,
+                warning: false,
+                file: "packs/foo/app/services/foo.rb".to_string(),
+                constant_name: "::Bar".to_string(),
+                referencing_pack_name: referencing_pack_name.to_string(),
+                defining_pack_name: "packs/bar".to_string(),
+            }
+        };
+
+        assert_eq!(
+            pack_set.responsible_pack_name(&identifier("privacy", "packs/foo")),
+            "packs/bar"
+        );
+        assert_eq!(
+            pack_set
+                .responsible_pack_name(&identifier("dependency", "packs/foo")),
+            "packs/foo"
+        );
+    }
 }
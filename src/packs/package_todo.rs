@@ -1,8 +1,16 @@
+use anyhow::{bail, Context};
+use itertools::Itertools;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
-use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use serde::{
+    de::DeserializeOwned, ser::SerializeMap, Deserialize, Serialize, Serializer,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
+use super::checker::ViolationIdentifier;
+use super::configuration::TodoStorage;
 use super::{pack::Pack, Configuration, Violation};
 
 #[derive(PartialEq, Debug, Eq, Deserialize, Serialize, Default, Clone)]
@@ -12,6 +20,14 @@ pub struct ViolationGroup {
     pub violation_types: HashSet<String>,
     #[serde(serialize_with = "serialize_sorted_set")]
     pub files: HashSet<String>,
+    // How many references each file makes to the constant, only recorded when
+    // `todo_occurrence_counts` is enabled. Files without a count allow any number of references.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub occurrences: BTreeMap<String, usize>,
+    // The pack making the references in each file, only recorded when it isn't the pack
+    // whose package_todo.yml this is, see `Pack::record_incoming_violations`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub referencing_packs: BTreeMap<String, String>,
 }
 
 fn serialize_sorted_set<S>(
@@ -76,99 +92,898 @@ where
     map_serializer.end()
 }
 
-pub fn package_todos_for_pack_name(
-    violations_by_responsible_pack_name: HashMap<String, Vec<Violation>>,
-) -> HashMap<String, PackageTodo> {
-    let mut ret = HashMap::new();
+// A recorded (constant, violation type, file) combination that `update` keeps as it is,
+// along with how many references it covers in its file, if that is recorded
+pub type CarriedOverViolation = (ViolationIdentifier, Option<usize>);
 
-    // Then we group violations by the defining pack, since that's how they're grouped in the package_todo.yml file
-    for (responsible_pack_name, mut violations) in
-        violations_by_responsible_pack_name
-    {
-        let mut violations_by_defining_pack: BTreeMap<
-            String,
-            BTreeMap<String, ViolationGroup>,
-        > = BTreeMap::new();
-        // Sort violations by the defining pack name, then constant name, then file name
-        // This ensures they show up deterministically in the package_todo.yml file.
-        violations.sort_by(|a, b| {
-            a.identifier
-                .defining_pack_name
-                .cmp(&b.identifier.defining_pack_name)
-                .then_with(|| {
-                    a.identifier.constant_name.cmp(&b.identifier.constant_name)
-                })
-                .then_with(|| a.identifier.file.cmp(&b.identifier.file))
-        });
-
-        for violation in violations {
-            let defining_pack_name =
-                violation.identifier.defining_pack_name.to_owned();
-            let existing_violations_by_constant_group =
-                violations_by_defining_pack
-                    .entry(defining_pack_name)
-                    .or_default();
-
-            let violation_group = existing_violations_by_constant_group
-                .entry(violation.identifier.constant_name.to_owned())
-                .or_default();
+fn package_todo_for_violations(
+    responsible_pack_name: &str,
+    violations: &[Violation],
+    carried_over: &[&CarriedOverViolation],
+    record_occurrences: bool,
+) -> PackageTodo {
+    // Each found violation is a distinct reference, keyed by identifier
+    let mut occurrence_counts: HashMap<&ViolationIdentifier, usize> =
+        HashMap::new();
+    for violation in violations {
+        *occurrence_counts.entry(&violation.identifier).or_default() += 1;
+    }
+    let entries = occurrence_counts
+        .into_iter()
+        .map(|(identifier, count)| {
+            (identifier, record_occurrences.then_some(count))
+        })
+        .chain(
+            carried_over
+                .iter()
+                .map(|(identifier, count)| (identifier, *count)),
+        );
 
-            violation_group
-                .files
-                .insert(violation.identifier.file.to_owned());
-            violation_group
-                .violation_types
-                .insert(violation.identifier.violation_type.to_owned());
+    // Violations are grouped by the defining pack, since that's how they're grouped in the
+    // package_todo.yml file. BTreeMaps keep them in a deterministic order.
+    let mut violations_by_defining_pack: BTreeMap<
+        String,
+        BTreeMap<String, ViolationGroup>,
+    > = BTreeMap::new();
+    for (identifier, count) in entries {
+        let violation_group = violations_by_defining_pack
+            .entry(identifier.defining_pack_name.to_owned())
+            .or_default()
+            .entry(identifier.constant_name.to_owned())
+            .or_default();
+
+        violation_group.files.insert(identifier.file.to_owned());
+        violation_group
+            .violation_types
+            .insert(identifier.violation_type.to_owned());
+        if identifier.referencing_pack_name != responsible_pack_name {
+            violation_group.referencing_packs.insert(
+                identifier.file.to_owned(),
+                identifier.referencing_pack_name.to_owned(),
+            );
         }
 
-        let package_todo = PackageTodo {
-            violations_by_defining_pack,
-        };
+        if let Some(count) = count {
+            // A reference can be several types of violation, so the count for a file is
+            // the largest count across violation types
+            let recorded_count = violation_group
+                .occurrences
+                .entry(identifier.file.to_owned())
+                .or_default();
+            *recorded_count = count.max(*recorded_count);
+        }
+    }
+
+    PackageTodo {
+        violations_by_defining_pack,
+    }
+}
 
-        ret.insert(responsible_pack_name, package_todo);
+impl PackageTodo {
+    // Each recorded (constant, violation type, file) combination, as seen by `check`.
+    // Files are referenced by the pack whose package_todo.yml this is, unless the entry
+    // records another referencing pack.
+    pub fn violation_identifiers(
+        &self,
+        pack_name: &str,
+    ) -> Vec<ViolationIdentifier> {
+        let mut violations = Vec::new();
+        for (defining_pack_name, violation_groups) in
+            &self.violations_by_defining_pack
+        {
+            for (constant_name, violation_group) in violation_groups {
+                for violation_type in &violation_group.violation_types {
+                    for file in &violation_group.files {
+                        let identifier = ViolationIdentifier {
+                            violation_type: violation_type.clone(),
+                            strict: false,
+                            warning: false,
+                            file: file.clone(),
+                            constant_name: constant_name.clone(),
+                            referencing_pack_name: violation_group
+                                .referencing_packs
+                                .get(file)
+                                .map_or(pack_name, String::as_str)
+                                .to_owned(),
+                            defining_pack_name: defining_pack_name.clone(),
+                        };
+
+                        violations.push(identifier);
+                    }
+                }
+            }
+        }
+        violations
     }
 
-    ret
+    // The number of references each recorded identifier covers, for files with an occurrence count
+    pub fn occurrence_limits(
+        &self,
+        pack_name: &str,
+    ) -> HashMap<ViolationIdentifier, usize> {
+        self.violation_identifiers(pack_name)
+            .into_iter()
+            .filter_map(|identifier| {
+                let count = self
+                    .violations_by_defining_pack
+                    .get(&identifier.defining_pack_name)?
+                    .get(&identifier.constant_name)?
+                    .occurrences
+                    .get(&identifier.file)?;
+                Some((identifier, *count))
+            })
+            .collect()
+    }
+
+    pub fn records_file(&self, file: &str) -> bool {
+        self.violations_by_defining_pack
+            .values()
+            .flat_map(|violation_groups| violation_groups.values())
+            .any(|violation_group| {
+                violation_group.files.iter().any(|f| f == file)
+            })
+    }
 }
-pub fn write_violations_to_disk(
+
+fn package_todos_for_violations(
     configuration: &Configuration,
     violations: HashSet<Violation>,
-) {
-    debug!("Starting writing violations to disk");
-    // First we need to group the violations by the repsonsible pack, which today is always the referencing pack
-    // Later if we change where a violation shows up, we should delegate to the checker
-    // to decide what pack it should be in.
+    carried_over: &[CarriedOverViolation],
+) -> HashMap<String, PackageTodo> {
+    // First we need to group the violations by the responsible pack, see `PackSet::responsible_pack_name`
+    let pack_set = &configuration.pack_set;
     let mut violations_by_responsible_pack: HashMap<String, Vec<Violation>> =
         HashMap::new();
     for violation in violations {
         if violation.identifier.strict {
             continue;
         }
-        let referencing_pack_name =
-            violation.identifier.referencing_pack_name.to_owned();
         violations_by_responsible_pack
-            .entry(referencing_pack_name)
+            .entry(
+                pack_set
+                    .responsible_pack_name(&violation.identifier)
+                    .to_owned(),
+            )
             .or_default()
             .push(violation);
     }
+    let mut carried_over_by_responsible_pack: HashMap<
+        String,
+        Vec<&CarriedOverViolation>,
+    > = HashMap::new();
+    for carried_over_violation in carried_over {
+        carried_over_by_responsible_pack
+            .entry(
+                pack_set
+                    .responsible_pack_name(&carried_over_violation.0)
+                    .to_owned(),
+            )
+            .or_default()
+            .push(carried_over_violation);
+    }
+
+    let responsible_pack_names: HashSet<&String> =
+        violations_by_responsible_pack
+            .keys()
+            .chain(carried_over_by_responsible_pack.keys())
+            .collect();
+    responsible_pack_names
+        .into_iter()
+        .map(|responsible_pack_name| {
+            let package_todo = package_todo_for_violations(
+                responsible_pack_name,
+                violations_by_responsible_pack
+                    .get(responsible_pack_name)
+                    .map_or(&[], Vec::as_slice),
+                carried_over_by_responsible_pack
+                    .get(responsible_pack_name)
+                    .map_or(&[], Vec::as_slice),
+                configuration.todo_occurrence_counts,
+            );
+            (responsible_pack_name.clone(), package_todo)
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PackageTodoChanges {
+    pub additions: Vec<ViolationIdentifier>,
+    pub removals: Vec<ViolationIdentifier>,
+    pub occurrence_changes: Vec<OccurrenceChange>,
+}
+
+// A recorded violation whose occurrence count would change
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct OccurrenceChange {
+    pub identifier: ViolationIdentifier,
+    pub recorded: usize,
+    pub updated: usize,
+}
+
+// The changes `update` would make to each pack's package_todo.yml, keyed by pack name.
+// Packs whose package_todo.yml would not change are omitted.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PackageTodoDiff {
+    pub changes_by_pack: BTreeMap<String, PackageTodoChanges>,
+}
+
+impl PackageTodoDiff {
+    // Occurrence count increases count as additions, since `check` would report them
+    pub fn has_additions(&self) -> bool {
+        self.changes_by_pack.values().any(|changes| {
+            !changes.additions.is_empty()
+                || changes
+                    .occurrence_changes
+                    .iter()
+                    .any(|change| change.updated > change.recorded)
+        })
+    }
+
+    fn count(&self, f: fn(&PackageTodoChanges) -> usize) -> usize {
+        self.changes_by_pack.values().map(f).sum()
+    }
+}
+
+impl Display for PackageTodoDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.changes_by_pack.is_empty() {
+            return write!(f, "No changes to package_todo.yml files");
+        }
+
+        for (pack_name, changes) in &self.changes_by_pack {
+            writeln!(f, "{}:\n{}", package_todo_path(pack_name), changes)?;
+        }
+
+        let occurrence_changes = self.count(|c| c.occurrence_changes.len());
+        if occurrence_changes > 0 {
+            write!(
+                f,
+                "{} addition(s), {} removal(s) and {} occurrence count change(s) to package_todo.yml files would be made",
+                self.count(|c| c.additions.len()),
+                self.count(|c| c.removals.len()),
+                occurrence_changes,
+            )
+        } else {
+            write!(
+                f,
+                "{} addition(s) and {} removal(s) to package_todo.yml files would be made",
+                self.count(|c| c.additions.len()),
+                self.count(|c| c.removals.len()),
+            )
+        }
+    }
+}
+
+impl Display for PackageTodoChanges {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (sign, identifiers) in
+            [("+", &self.additions), ("-", &self.removals)]
+        {
+            for identifier in identifiers {
+                writeln!(
+                    f,
+                    "{} {} `{}` (defined in `{}`) in {}",
+                    sign,
+                    identifier.violation_type,
+                    identifier.constant_name,
+                    identifier.defining_pack_name,
+                    identifier.file,
+                )?;
+            }
+        }
+        for change in &self.occurrence_changes {
+            writeln!(
+                f,
+                "~ {} `{}` (defined in `{}`) in {}: {} -> {} occurrence(s)",
+                change.identifier.violation_type,
+                change.identifier.constant_name,
+                change.identifier.defining_pack_name,
+                change.identifier.file,
+                change.recorded,
+                change.updated,
+            )?;
+        }
+        Ok(())
+    }
+}
 
+pub fn package_todo_path(pack_name: &str) -> String {
+    if pack_name == "." {
+        String::from("package_todo.yml")
+    } else {
+        format!("{}/package_todo.yml", pack_name)
+    }
+}
+
+fn compare_identifiers(
+    a: &ViolationIdentifier,
+    b: &ViolationIdentifier,
+) -> std::cmp::Ordering {
+    a.defining_pack_name
+        .cmp(&b.defining_pack_name)
+        .then_with(|| a.constant_name.cmp(&b.constant_name))
+        .then_with(|| a.file.cmp(&b.file))
+        .then_with(|| a.violation_type.cmp(&b.violation_type))
+}
+
+// Compares what `write_violations_to_disk` would write against the recorded violations.
+// Both sides are expanded from a PackageTodo, so entries that only exist because of how
+// violations are grouped in package_todo.yml files do not show up as changes.
+pub fn diff_violations_with_disk(
+    configuration: &Configuration,
+    violations: HashSet<Violation>,
+    carried_over: &[CarriedOverViolation],
+    responsible_pack_names: &HashSet<String>,
+) -> PackageTodoDiff {
     let package_todos_by_pack_name =
-        package_todos_for_pack_name(violations_by_responsible_pack);
-
-    let all_packs = &configuration.pack_set.packs;
-    all_packs.par_iter().for_each(|p| {
-        let package_todo = package_todos_by_pack_name.get(&p.name);
-        match package_todo {
-            Some(package_todo) => write_package_todo_to_disk(
-                p,
-                package_todo,
+        package_todos_for_violations(configuration, violations, carried_over);
+
+    let mut changes_by_pack = BTreeMap::new();
+    for pack in &configuration.pack_set.packs {
+        if !responsible_pack_names.contains(&pack.name) {
+            continue;
+        }
+        let updated_package_todo = package_todos_by_pack_name
+            .get(&pack.name)
+            .cloned()
+            .unwrap_or_default();
+        let recorded: HashSet<ViolationIdentifier> = pack
+            .package_todo
+            .violation_identifiers(&pack.name)
+            .into_iter()
+            .collect();
+        let updated: HashSet<ViolationIdentifier> = updated_package_todo
+            .violation_identifiers(&pack.name)
+            .into_iter()
+            .collect();
+
+        let recorded_occurrences =
+            pack.package_todo.occurrence_limits(&pack.name);
+        let mut occurrence_changes: Vec<OccurrenceChange> =
+            updated_package_todo
+                .occurrence_limits(&pack.name)
+                .into_iter()
+                .filter_map(|(identifier, updated)| {
+                    let recorded = *recorded_occurrences.get(&identifier)?;
+                    (recorded != updated).then_some(OccurrenceChange {
+                        identifier,
+                        recorded,
+                        updated,
+                    })
+                })
+                .collect();
+        occurrence_changes
+            .sort_by(|a, b| compare_identifiers(&a.identifier, &b.identifier));
+
+        let mut changes = PackageTodoChanges {
+            additions: updated.difference(&recorded).cloned().collect(),
+            removals: recorded.difference(&updated).cloned().collect(),
+            occurrence_changes,
+        };
+        if changes.additions.is_empty()
+            && changes.removals.is_empty()
+            && changes.occurrence_changes.is_empty()
+        {
+            continue;
+        }
+        for identifiers in [&mut changes.additions, &mut changes.removals] {
+            identifiers.sort_by(compare_identifiers);
+        }
+        changes_by_pack.insert(pack.name.clone(), changes);
+    }
+
+    PackageTodoDiff { changes_by_pack }
+}
+
+// Rewrites the package_todo.yml of each responsible pack. Other packs' files are left untouched.
+// Returns the package todos by pack name, as written.
+pub fn write_violations_to_disk(
+    configuration: &Configuration,
+    violations: HashSet<Violation>,
+    carried_over: &[CarriedOverViolation],
+    responsible_pack_names: &HashSet<String>,
+) -> anyhow::Result<HashMap<String, PackageTodo>> {
+    debug!("Starting writing violations to disk");
+    let package_todos_by_pack_name =
+        package_todos_for_violations(configuration, violations, carried_over);
+
+    match configuration.todo_storage {
+        TodoStorage::PerPack => {
+            let responsible_packs: Vec<&Pack> = configuration
+                .pack_set
+                .packs
+                .iter()
+                .filter(|p| responsible_pack_names.contains(&p.name))
+                .collect();
+            responsible_packs.par_iter().try_for_each(|p| {
+                let package_todo = package_todos_by_pack_name.get(&p.name);
+                match package_todo {
+                    Some(package_todo) => write_package_todo_to_disk(
+                        p,
+                        package_todo,
+                        configuration.packs_first_mode,
+                    ),
+                    None => delete_package_todo_from_disk(p),
+                }
+            })?;
+        }
+        TodoStorage::Central => {
+            // Packs that are not responsible keep what they have recorded
+            let package_todos_by_pack_name = configuration
+                .pack_set
+                .packs
+                .iter()
+                .filter_map(|p| {
+                    let package_todo =
+                        if responsible_pack_names.contains(&p.name) {
+                            package_todos_by_pack_name.get(&p.name)?
+                        } else {
+                            &p.package_todo
+                        };
+                    (!package_todo.violations_by_defining_pack.is_empty())
+                        .then(|| (p.name.clone(), package_todo.clone()))
+                })
+                .collect();
+            write_central_package_todo_to_disk(
+                &configuration.absolute_root,
+                &CentralPackageTodo {
+                    package_todos_by_pack_name,
+                },
                 configuration.packs_first_mode,
-            ),
-            None => delete_package_todo_from_disk(p),
+            )?;
         }
-    });
+    }
 
     debug!("Finished writing violations to disk");
+
+    Ok(package_todos_by_pack_name)
+}
+
+fn violation_group<'a>(
+    package_todo: &'a PackageTodo,
+    defining_pack_name: &str,
+    constant_name: &str,
+) -> Option<&'a ViolationGroup> {
+    package_todo
+        .violations_by_defining_pack
+        .get(defining_pack_name)?
+        .get(constant_name)
+}
+
+// A recorded (defining pack, constant, file, violation type) combination
+type TodoEntry<'a> = (&'a String, &'a String, &'a String, &'a String);
+
+// A `ViolationGroup` records each of its violation types for each of its files
+fn todo_entries(package_todo: &PackageTodo) -> HashSet<TodoEntry<'_>> {
+    package_todo
+        .violations_by_defining_pack
+        .iter()
+        .flat_map(|(defining_pack_name, groups)| {
+            groups.iter().flat_map(move |(constant_name, group)| {
+                group.files.iter().flat_map(move |file| {
+                    group.violation_types.iter().map(move |violation_type| {
+                        (
+                            defining_pack_name,
+                            constant_name,
+                            file,
+                            violation_type,
+                        )
+                    })
+                })
+            })
+        })
+        .collect()
+}
+
+// Merges both sides of a merge with their common ancestor. Entries in the ancestor that
+// either side removed stay removed, e.g. after fixing the violation, and the union of the
+// remaining entries is kept, except entries for files that no longer exist. Entries are
+// merged per file and violation type, then grouped by constant again.
+fn merge_package_todos(
+    ancestor: &PackageTodo,
+    current: &PackageTodo,
+    other: &PackageTodo,
+    file_exists: impl Fn(&str) -> bool,
+) -> PackageTodo {
+    let [ancestor_entries, current_entries, other_entries] =
+        [ancestor, current, other].map(todo_entries);
+    let removed_on_either_side = |entry: &TodoEntry| {
+        ancestor_entries.contains(entry)
+            && !(current_entries.contains(entry)
+                && other_entries.contains(entry))
+    };
+
+    let mut violations_by_defining_pack: BTreeMap<
+        String,
+        BTreeMap<String, ViolationGroup>,
+    > = BTreeMap::new();
+    for entry in current_entries.union(&other_entries) {
+        let (defining_pack_name, constant_name, file, violation_type) = *entry;
+        if !file_exists(file) || removed_on_either_side(entry) {
+            continue;
+        }
+        let group = violations_by_defining_pack
+            .entry(defining_pack_name.clone())
+            .or_default()
+            .entry(constant_name.clone())
+            .or_default();
+        group.files.insert(file.clone());
+        group.violation_types.insert(violation_type.clone());
+    }
+
+    for (defining_pack_name, groups) in &mut violations_by_defining_pack {
+        for (constant_name, group) in groups {
+            for file in &group.files {
+                let side_groups: Vec<&ViolationGroup> = [current, other]
+                    .into_iter()
+                    .filter_map(|todo| {
+                        violation_group(todo, defining_pack_name, constant_name)
+                    })
+                    .filter(|side_group| side_group.files.contains(file))
+                    .collect();
+                if let Some(count) = side_groups
+                    .iter()
+                    .map(|side_group| side_group.occurrences.get(file).copied())
+                    .reduce(merge_occurrences)
+                    .flatten()
+                {
+                    group.occurrences.insert(file.clone(), count);
+                }
+                if let Some(referencing_pack) =
+                    side_groups.iter().find_map(|side_group| {
+                        side_group.referencing_packs.get(file)
+                    })
+                {
+                    group
+                        .referencing_packs
+                        .insert(file.clone(), referencing_pack.clone());
+                }
+            }
+        }
+    }
+
+    PackageTodo {
+        violations_by_defining_pack,
+    }
+}
+
+// The count of a file recorded on both sides of a merge. Keeps the larger count so
+// neither side's references are reported as new violations, and no count at all when
+// either side allows any number of references.
+fn merge_occurrences(
+    current: Option<usize>,
+    other: Option<usize>,
+) -> Option<usize> {
+    Some(current?.max(other?))
+}
+
+fn read_for_merge<T: DeserializeOwned + Default>(
+    path: &Path,
+) -> anyhow::Result<T> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    // A version is empty when its side has no recorded violations left
+    let has_entries = contents.lines().any(|line| {
+        let line = line.trim();
+        !line.is_empty() && !line.starts_with('#') && line != "---"
+    });
+    if !has_entries {
+        return Ok(T::default());
+    }
+    serde_yaml::from_str(&contents)
+        .with_context(|| format!("Failed to deserialize {}", path.display()))
+}
+
+// The responsible pack is only recorded in the header comment, see `header`
+fn responsible_pack_name_from_header(contents: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        line.strip_prefix("# '")
+            .and_then(|rest| rest.strip_suffix("' package."))
+            .map(String::from)
+    })
+}
+
+// Entry point of the `merge-todo` git merge driver: merges the three versions of a
+// package_todo.yml and writes the result to `current`, as git expects.
+pub fn merge_package_todo_files(
+    configuration: &Configuration,
+    ancestor: &Path,
+    current: &Path,
+    other: &Path,
+) -> anyhow::Result<()> {
+    let file_exists =
+        |file: &str| configuration.absolute_root.join(file).exists();
+
+    let merged_yml = match configuration.todo_storage {
+        TodoStorage::PerPack => {
+            let responsible_pack_name = [current, other, ancestor]
+                .iter()
+                .filter_map(|path| std::fs::read_to_string(path).ok())
+                .find_map(|contents| {
+                    responsible_pack_name_from_header(&contents)
+                })
+                .context(
+                    "Could not find the name of the pack in the package_todo.yml header",
+                )?;
+
+            let merged = merge_package_todos(
+                &read_for_merge(ancestor)?,
+                &read_for_merge(current)?,
+                &read_for_merge(other)?,
+                file_exists,
+            );
+            serialize_package_todo(
+                &responsible_pack_name,
+                &merged,
+                configuration.packs_first_mode,
+            )
+        }
+        TodoStorage::Central => {
+            let [ancestor, current, other]: [CentralPackageTodo; 3] = [
+                read_for_merge(ancestor)?,
+                read_for_merge(current)?,
+                read_for_merge(other)?,
+            ];
+            let empty_package_todo = PackageTodo::default();
+            let package_todo_for =
+                |central: &CentralPackageTodo, pack_name: &str| {
+                    central
+                        .package_todos_by_pack_name
+                        .get(pack_name)
+                        .cloned()
+                        .unwrap_or_else(|| empty_package_todo.clone())
+                };
+
+            let pack_names: BTreeSet<&String> = current
+                .package_todos_by_pack_name
+                .keys()
+                .chain(other.package_todos_by_pack_name.keys())
+                .collect();
+            let package_todos_by_pack_name = pack_names
+                .into_iter()
+                .map(|pack_name| {
+                    let merged = merge_package_todos(
+                        &package_todo_for(&ancestor, pack_name),
+                        &package_todo_for(&current, pack_name),
+                        &package_todo_for(&other, pack_name),
+                        file_exists,
+                    );
+                    (pack_name.clone(), merged)
+                })
+                .filter(|(_, package_todo)| {
+                    !package_todo.violations_by_defining_pack.is_empty()
+                })
+                .collect();
+            serialize_central_package_todo(
+                &CentralPackageTodo {
+                    package_todos_by_pack_name,
+                },
+                configuration.packs_first_mode,
+            )
+        }
+    };
+
+    std::fs::write(current, merged_yml)
+        .with_context(|| format!("Failed to write {}", current.display()))
+}
+
+pub const CENTRAL_PACKAGE_TODO_FILE_NAME: &str = "packs_todo.yml";
+
+// All recorded violations when `todo_storage: central` is set, keyed by the responsible pack
+#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, Default, Clone)]
+pub struct CentralPackageTodo {
+    #[serde(flatten)]
+    pub package_todos_by_pack_name: BTreeMap<String, PackageTodo>,
+}
+
+pub(crate) fn read_package_todo_file(
+    absolute_path: &Path,
+) -> anyhow::Result<PackageTodo> {
+    if !absolute_path.exists() {
+        return Ok(PackageTodo::default());
+    }
+    let contents = std::fs::read_to_string(absolute_path)
+        .context("Could not read the package_todo.yml file")?;
+    serde_yaml::from_str(&contents).with_context(|| {
+        format!(
+            "Failed to deserialize the package_todo.yml file at {}. Try deleting the file and running the `update` command to regenerate it.",
+            absolute_path.display()
+        )
+    })
+}
+
+fn per_pack_package_todo_path(pack: &Pack) -> PathBuf {
+    pack.yml.parent().unwrap().join("package_todo.yml")
+}
+
+fn central_package_todo_path(absolute_root: &Path) -> PathBuf {
+    absolute_root.join(CENTRAL_PACKAGE_TODO_FILE_NAME)
+}
+
+fn read_central_package_todo(
+    absolute_root: &Path,
+) -> anyhow::Result<CentralPackageTodo> {
+    let path = central_package_todo_path(absolute_root);
+    if !path.exists() {
+        return Ok(CentralPackageTodo::default());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    serde_yaml::from_str(&contents).with_context(|| {
+        format!(
+            "Failed to deserialize {}. Try deleting the file and running the `update` command to regenerate it.",
+            path.display()
+        )
+    })
+}
+
+// Replaces each pack's package_todo with its entry in the central file
+pub(crate) fn with_central_package_todos(
+    absolute_root: &Path,
+    packs: HashSet<Pack>,
+) -> anyhow::Result<HashSet<Pack>> {
+    let mut central = read_central_package_todo(absolute_root)?;
+    Ok(packs
+        .into_iter()
+        .map(|pack| {
+            let package_todo = central
+                .package_todos_by_pack_name
+                .remove(&pack.name)
+                .unwrap_or_default();
+            Pack {
+                package_todo,
+                ..pack
+            }
+        })
+        .collect())
+}
+
+fn serialize_central_package_todo(
+    central: &CentralPackageTodo,
+    packs_first_mode: bool,
+) -> String {
+    // See `serialize_violations_by_defining_pack` for why the root pack is written as `#.#`
+    let package_todos_by_pack_name: BTreeMap<&str, &PackageTodo> = central
+        .package_todos_by_pack_name
+        .iter()
+        .map(|(pack_name, package_todo)| {
+            let key = if pack_name == "." { "#.#" } else { pack_name };
+            (key, package_todo)
+        })
+        .collect();
+    let yml = serde_yaml::to_string(&package_todos_by_pack_name).unwrap();
+
+    // HACK: See `serialize_package_todo`
+    let yml = yml.replace("'#", "\"").replace("#'", "\"");
+    central_header(packs_first_mode) + &yml
+}
+
+fn write_central_package_todo_to_disk(
+    absolute_root: &Path,
+    central: &CentralPackageTodo,
+    packs_first_mode: bool,
+) -> anyhow::Result<()> {
+    let path = central_package_todo_path(absolute_root);
+    if central.package_todos_by_pack_name.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path).with_context(|| {
+                format!("Failed to delete {}", path.display())
+            })?;
+        }
+        return Ok(());
+    }
+    std::fs::write(
+        &path,
+        serialize_central_package_todo(central, packs_first_mode),
+    )
+    .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+// Moves all recorded violations to the `target` storage. Violations are read from the
+// other storage regardless of the configured `todo_storage`, so this can be run before
+// or after changing packs.yml. Violations already in the `target` storage are kept, and
+// merged with the moved ones, so running it twice loses nothing.
+pub fn convert_todo_storage(
+    configuration: &Configuration,
+    target: TodoStorage,
+) -> anyhow::Result<()> {
+    let packs = &configuration.pack_set.packs;
+    let absolute_root = &configuration.absolute_root;
+    let all_files_exist = |_: &str| true;
+
+    match target {
+        TodoStorage::Central => {
+            let mut central = read_central_package_todo(absolute_root)?;
+            let mut moved_pack_count = 0;
+            for pack in packs {
+                let package_todo =
+                    read_package_todo_file(&per_pack_package_todo_path(pack))?;
+                if package_todo.violations_by_defining_pack.is_empty() {
+                    continue;
+                }
+                let merged = merge_package_todos(
+                    &PackageTodo::default(),
+                    &central
+                        .package_todos_by_pack_name
+                        .remove(&pack.name)
+                        .unwrap_or_default(),
+                    &package_todo,
+                    all_files_exist,
+                );
+                central
+                    .package_todos_by_pack_name
+                    .insert(pack.name.clone(), merged);
+                moved_pack_count += 1;
+            }
+            write_central_package_todo_to_disk(
+                absolute_root,
+                &central,
+                configuration.packs_first_mode,
+            )?;
+            for pack in packs {
+                delete_package_todo_from_disk(pack)?;
+            }
+            println!(
+                "Moved the package_todo.yml files of {} pack(s) into {}",
+                moved_pack_count, CENTRAL_PACKAGE_TODO_FILE_NAME
+            );
+        }
+        TodoStorage::PerPack => {
+            let central = read_central_package_todo(absolute_root)?;
+            let unknown_pack_names: Vec<&String> = central
+                .package_todos_by_pack_name
+                .keys()
+                .filter(|pack_name| {
+                    !packs.iter().any(|pack| &&pack.name == pack_name)
+                })
+                .collect();
+            if !unknown_pack_names.is_empty() {
+                bail!(
+                    "{} has violations recorded for packs that do not exist: {}",
+                    CENTRAL_PACKAGE_TODO_FILE_NAME,
+                    unknown_pack_names.iter().join(", ")
+                );
+            }
+            for pack in packs {
+                if let Some(package_todo) =
+                    central.package_todos_by_pack_name.get(&pack.name)
+                {
+                    let merged = merge_package_todos(
+                        &PackageTodo::default(),
+                        &read_package_todo_file(&per_pack_package_todo_path(
+                            pack,
+                        ))?,
+                        package_todo,
+                        all_files_exist,
+                    );
+                    write_package_todo_to_disk(
+                        pack,
+                        &merged,
+                        configuration.packs_first_mode,
+                    )?;
+                }
+            }
+            write_central_package_todo_to_disk(
+                absolute_root,
+                &CentralPackageTodo::default(),
+                configuration.packs_first_mode,
+            )?;
+            println!(
+                "Moved the recorded violations of {} pack(s) from {} into package_todo.yml files",
+                central.package_todos_by_pack_name.len(),
+                CENTRAL_PACKAGE_TODO_FILE_NAME
+            );
+        }
+    }
+
+    if configuration.todo_storage != target {
+        let setting = match target {
+            TodoStorage::PerPack => "per_pack",
+            TodoStorage::Central => "central",
+        };
+        println!("Set `todo_storage: {}` in packs.yml to use them", setting);
+    }
+    Ok(())
 }
 
 fn serialize_package_todo(
@@ -189,16 +1004,9 @@ fn write_package_todo_to_disk(
     responsible_pack: &Pack,
     package_todo: &PackageTodo,
     packs_first_mode: bool,
-) {
-    let package_todo_yml_absolute_filepath = responsible_pack
-        .yml
-        .parent()
-        .unwrap()
-        .join("package_todo.yml");
-
-    if !package_todo_yml_absolute_filepath.exists() {
-        std::fs::File::create(&package_todo_yml_absolute_filepath).unwrap();
-    }
+) -> anyhow::Result<()> {
+    let package_todo_yml_absolute_filepath =
+        per_pack_package_todo_path(responsible_pack);
 
     let package_todo_yml = serialize_package_todo(
         &responsible_pack.name,
@@ -206,30 +1014,42 @@ fn write_package_todo_to_disk(
         packs_first_mode,
     );
 
-    std::fs::write(package_todo_yml_absolute_filepath, package_todo_yml)
-        .unwrap();
+    std::fs::write(&package_todo_yml_absolute_filepath, package_todo_yml)
+        .with_context(|| {
+            format!(
+                "Failed to write {}",
+                package_todo_yml_absolute_filepath.display()
+            )
+        })
 }
 
-fn delete_package_todo_from_disk(responsible_pack: &Pack) {
-    let package_todo_yml_absolute_filepath = responsible_pack
-        .yml
-        .parent()
-        .unwrap()
-        .join("package_todo.yml");
+fn delete_package_todo_from_disk(
+    responsible_pack: &Pack,
+) -> anyhow::Result<()> {
+    let package_todo_yml_absolute_filepath =
+        per_pack_package_todo_path(responsible_pack);
 
     if package_todo_yml_absolute_filepath.exists() {
-        // Delete package_todo_yml_absolute_filepath
-        std::fs::remove_file(package_todo_yml_absolute_filepath).unwrap();
+        std::fs::remove_file(&package_todo_yml_absolute_filepath)
+            .with_context(|| {
+                format!(
+                    "Failed to delete {}",
+                    package_todo_yml_absolute_filepath.display()
+                )
+            })?;
     }
+    Ok(())
 }
 
-fn header(responsible_pack_name: &String, packs_first_mode: bool) -> String {
-    let command = if packs_first_mode {
+fn update_command(packs_first_mode: bool) -> &'static str {
+    if packs_first_mode {
         "pks update"
     } else {
         "bin/packwerk update-todo"
-    };
+    }
+}
 
+fn header(responsible_pack_name: &String, packs_first_mode: bool) -> String {
     format!("\
 # This file contains a list of dependencies that are not part of the long term plan for the
 # '{}' package.
@@ -239,7 +1059,20 @@ fn header(responsible_pack_name: &String, packs_first_mode: bool) -> String {
 #
 # {}
 ---
-", responsible_pack_name, command)
+", responsible_pack_name, update_command(packs_first_mode))
+}
+
+fn central_header(packs_first_mode: bool) -> String {
+    format!("\
+# This file contains a list of dependencies that are not part of the long term plan,
+# grouped by the package that is responsible for them.
+# We should generally work to reduce this list over time.
+#
+# You can regenerate this file using the following command:
+#
+# {}
+---
+", update_command(packs_first_mode))
 }
 
 #[cfg(test)]
@@ -268,6 +1101,7 @@ mod tests {
             ViolationGroup {
                 violation_types,
                 files,
+                ..ViolationGroup::default()
             },
         );
 
@@ -482,4 +1316,183 @@ packs/bar:
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_merge_package_todos() {
+        let todo = |constant_name: &str, files: Vec<&str>| PackageTodo {
+            violations_by_defining_pack: [(
+                String::from("packs/bar"),
+                construct_violations(
+                    String::from(constant_name),
+                    vec![String::from("dependency")],
+                    files.into_iter().map(String::from).collect(),
+                ),
+            )]
+            .into(),
+        };
+        let current = todo("::Bar", vec!["a.rb", "c.rb"]);
+        // Includes the deleted e.rb
+        let mut other = todo("::Bar", vec!["a.rb", "b.rb", "d.rb", "e.rb"]);
+        other
+            .violations_by_defining_pack
+            .get_mut("packs/bar")
+            .unwrap()
+            .extend(baz_violations());
+
+        let merged = merge_package_todos(
+            &PackageTodo::default(),
+            &current,
+            &other,
+            |file| file != "e.rb",
+        );
+
+        let mut expected = todo("::Bar", vec!["a.rb", "b.rb", "c.rb", "d.rb"]);
+        expected
+            .violations_by_defining_pack
+            .get_mut("packs/bar")
+            .unwrap()
+            .extend(baz_violations());
+        assert_eq!(expected, merged);
+    }
+
+    #[test]
+    fn test_merge_package_todos_drops_entries_removed_on_either_side() {
+        let todo = |files: Vec<&str>| PackageTodo {
+            violations_by_defining_pack: [(
+                String::from("packs/bar"),
+                construct_violations(
+                    String::from("::Bar"),
+                    vec![String::from("dependency")],
+                    files.into_iter().map(String::from).collect(),
+                ),
+            )]
+            .into(),
+        };
+        // a.rb was fixed on the current side and b.rb on the other side, and all
+        // ::Baz violations were fixed on the current side
+        let mut ancestor = todo(vec!["a.rb", "b.rb", "c.rb"]);
+        let current = todo(vec!["b.rb", "c.rb", "d.rb"]);
+        let mut other = todo(vec!["a.rb", "c.rb"]);
+        for todo in [&mut ancestor, &mut other] {
+            todo.violations_by_defining_pack
+                .get_mut("packs/bar")
+                .unwrap()
+                .extend(baz_violations());
+        }
+
+        let merged = merge_package_todos(&ancestor, &current, &other, |_| true);
+
+        assert_eq!(todo(vec!["c.rb", "d.rb"]), merged);
+    }
+
+    #[test]
+    fn test_merge_package_todos_per_file_and_violation_type() {
+        let todo = |types: Vec<&str>, files: Vec<&str>| PackageTodo {
+            violations_by_defining_pack: [(
+                String::from("packs/bar"),
+                construct_violations(
+                    String::from("::Bar"),
+                    types.into_iter().map(String::from).collect(),
+                    files.into_iter().map(String::from).collect(),
+                ),
+            )]
+            .into(),
+        };
+        // privacy was fixed in a.rb on the current side, while the other side added
+        // b.rb with a privacy violation
+        let ancestor = todo(vec!["dependency", "privacy"], vec!["a.rb"]);
+        let current = todo(vec!["dependency"], vec!["a.rb"]);
+        let other = todo(vec!["dependency", "privacy"], vec!["a.rb", "b.rb"]);
+
+        let merged = merge_package_todos(&ancestor, &current, &other, |_| true);
+
+        assert_eq!(
+            todo(vec!["dependency", "privacy"], vec!["a.rb", "b.rb"]),
+            merged
+        );
+    }
+
+    #[test]
+    fn test_violation_identifiers_with_referencing_packs() {
+        let mut package_todo = PackageTodo {
+            violations_by_defining_pack: [(
+                String::from("packs/bar"),
+                construct_violations(
+                    String::from("::Bar"),
+                    vec![String::from("privacy")],
+                    vec![String::from("packs/foo/app/services/foo.rb")],
+                ),
+            )]
+            .into(),
+        };
+        let referencing_pack_names = |package_todo: &PackageTodo| {
+            package_todo
+                .violation_identifiers("packs/bar")
+                .into_iter()
+                .map(|identifier| identifier.referencing_pack_name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(referencing_pack_names(&package_todo), vec!["packs/bar"]);
+
+        package_todo
+            .violations_by_defining_pack
+            .get_mut("packs/bar")
+            .unwrap()
+            .get_mut("::Bar")
+            .unwrap()
+            .referencing_packs
+            .insert(
+                String::from("packs/foo/app/services/foo.rb"),
+                String::from("packs/foo"),
+            );
+        assert_eq!(referencing_pack_names(&package_todo), vec!["packs/foo"]);
+    }
+
+    #[test]
+    fn test_responsible_pack_name_from_header() {
+        assert_eq!(
+            responsible_pack_name_from_header(&header(
+                &String::from("packs/foo"),
+                true
+            )),
+            Some(String::from("packs/foo"))
+        );
+        assert_eq!(responsible_pack_name_from_header("packs/bar: {}"), None);
+    }
+
+    #[test]
+    fn test_serialize_central_package_todo_round_trip() {
+        let central = CentralPackageTodo {
+            package_todos_by_pack_name: [
+                (String::from("."), example_package_todo(String::from("."))),
+                (
+                    String::from("packs/foo"),
+                    example_package_todo(String::from("packs/bar")),
+                ),
+            ]
+            .into(),
+        };
+
+        let serialized = serialize_central_package_todo(&central, true);
+        assert!(serialized.contains("\n# pks update\n"));
+        assert!(serialized.contains("\n\".\":\n  \".\":\n    \"::Bar\":\n"));
+        assert!(
+            serialized.contains("\npacks/foo:\n  packs/bar:\n    \"::Bar\":\n")
+        );
+
+        let deserialized: CentralPackageTodo =
+            serde_yaml::from_str(&serialized).unwrap();
+        assert_eq!(central, deserialized);
+
+        assert!(serialize_central_package_todo(&central, false)
+            .contains("\n# bin/packwerk update-todo\n"));
+    }
+
+    #[test]
+    fn test_merge_occurrences() {
+        assert_eq!(merge_occurrences(Some(1), Some(3)), Some(3));
+        assert_eq!(merge_occurrences(Some(2), Some(1)), Some(2));
+        assert_eq!(merge_occurrences(Some(2), None), None);
+        assert_eq!(merge_occurrences(None, None), None);
+    }
 }
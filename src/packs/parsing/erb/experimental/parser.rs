@@ -41,5 +41,6 @@ pub(crate) fn process_from_contents(
         unresolved_references: references_without_range,
         definitions: vec![],
         sigils: vec![],
+        suppressions: vec![],
     }
 }
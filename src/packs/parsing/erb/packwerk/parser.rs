@@ -42,5 +42,7 @@ pub(crate) fn process_from_contents(
         unresolved_references: references_without_range,
         definitions: vec![],
         sigils,
+        // ERB references have no line numbers to suppress violations at
+        suppressions: vec![],
     }
 }
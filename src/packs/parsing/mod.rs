@@ -54,6 +54,7 @@ pub fn process_file(
             unresolved_references: vec![],
             definitions: vec![], // TODO
             sigils: vec![],
+            suppressions: vec![],
         })
     };
 
@@ -52,7 +52,7 @@ mod tests {
     use crate::packs::parsing::ruby::experimental::parser::process_from_contents;
     use crate::packs::parsing::{ParsedDefinition, Range};
     use crate::packs::{
-        Configuration, ProcessedFile, Sigil, UnresolvedReference,
+        Configuration, ProcessedFile, Sigil, SigilValue, UnresolvedReference,
     };
     use pretty_assertions::assert_eq;
 
@@ -82,6 +82,7 @@ mod tests {
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -112,6 +113,7 @@ mod tests {
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -143,6 +145,7 @@ mod tests {
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -174,6 +177,7 @@ mod tests {
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -201,6 +205,7 @@ end
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -238,6 +243,7 @@ end
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -275,6 +281,7 @@ end
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -311,6 +318,7 @@ end
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -351,6 +359,7 @@ end
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
         assert_eq!(expected, actual);
     }
@@ -392,6 +401,7 @@ end
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
 
         assert_eq!(expected, actual);
@@ -434,6 +444,7 @@ end
             unresolved_references,
             definitions,
             sigils: vec![],
+            suppressions: vec![],
         };
 
         assert_eq!(expected, actual);
@@ -478,7 +489,7 @@ end
             .sigils,
             vec![Sigil {
                 name: String::from("public"),
-                value: true,
+                value: SigilValue::Bool(true),
             }]
         );
     }
@@ -1,5 +1,7 @@
 use crate::packs::file_utils::file_read_contents;
-use crate::packs::parsing::ruby::parse_utils::extract_sigils_from_contents;
+use crate::packs::parsing::ruby::parse_utils::{
+    extract_sigils_from_contents, extract_suppressions_from_contents,
+};
 use crate::packs::{
     parsing::{
         ruby::parse_utils::{
@@ -215,6 +217,7 @@ pub(crate) fn process_from_contents(
                 unresolved_references: vec![],
                 definitions: vec![],
                 sigils: vec![],
+                suppressions: vec![],
             }
         }
     };
@@ -240,11 +243,13 @@ pub(crate) fn process_from_contents(
     let definitions = collector.definitions;
 
     let sigils = extract_sigils_from_contents(&contents);
+    let suppressions = extract_suppressions_from_contents(&contents);
 
     ProcessedFile {
         absolute_path,
         unresolved_references,
         definitions,
         sigils,
+        suppressions,
     }
 }
@@ -6,7 +6,7 @@ mod tests {
 
     use crate::packs::parsing::ruby::packwerk::parser::process_from_contents;
     use crate::packs::parsing::Range;
-    use crate::packs::{Configuration, Sigil, UnresolvedReference};
+    use crate::packs::{Configuration, Sigil, SigilValue, UnresolvedReference};
 
     #[test]
     fn trivial_case() {
@@ -1350,7 +1350,7 @@ end
             .sigils,
             vec![Sigil {
                 name: String::from("public"),
-                value: true,
+                value: SigilValue::Bool(true),
             }]
         );
     }
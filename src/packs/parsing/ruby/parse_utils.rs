@@ -5,7 +5,7 @@ use line_col::LineColLookup;
 
 use crate::packs::{
     parsing::{ParsedDefinition, Range, UnresolvedReference},
-    Sigil,
+    Sigil, SigilValue, Suppression,
 };
 
 use super::inflector_shim::to_class_case;
@@ -210,18 +210,162 @@ pub fn get_constant_assignment_definition(
     })
 }
 
+// Sigils are comments of the form `# pack_<name>: <value>` in the first five lines of a
+// file. Values are `true`/`false`, a `[a, b]` list, or any other (optionally quoted) string,
+// and can be followed by a comment.
 pub fn extract_sigils_from_contents(contents: &str) -> Vec<Sigil> {
-    let mut sigils: Vec<Sigil> = Vec::new();
-
-    // Hardcoded to public, but later we can make this a convention like `pack_*: true`, if we find it more generally useful
-    contents.lines().take(5).for_each(|line| {
-        if line.contains("pack_public: true") {
-            sigils.push(Sigil {
-                name: "public".to_string(),
-                value: true,
-            });
-        }
-    });
+    contents.lines().take(5).filter_map(parse_sigil).collect()
+}
+
+fn parse_sigil(line: &str) -> Option<Sigil> {
+    let comment = line.trim_start().strip_prefix('#')?.trim_start();
+    let (name, value) = comment.strip_prefix("pack_")?.split_once(':')?;
+    if name.is_empty()
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+
+    // Drop a trailing comment, e.g. `# pack_public: true # legacy`
+    let value = value.split_once(" #").map_or(value, |(value, _)| value);
+    let value = value.trim();
+    let value = match value {
+        "true" => SigilValue::Bool(true),
+        "false" => SigilValue::Bool(false),
+        _ => match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            Some(list) => SigilValue::List(
+                list.split(',')
+                    .map(unquote)
+                    .filter(|item| !item.is_empty())
+                    .map(String::from)
+                    .collect(),
+            ),
+            None => SigilValue::String(unquote(value).to_owned()),
+        },
+    };
+    Some(Sigil {
+        name: name.to_owned(),
+        value,
+    })
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim().trim_matches(|c| c == '"' || c == '\'')
+}
 
-    sigils
+const DISABLE_NEXT_LINE_DIRECTIVE: &str = "# packs:disable-next-line";
+const DISABLE_DIRECTIVE: &str = "# packs:disable";
+
+// Parses `# packs:disable-next-line <types>` and `# packs:disable <types>` comments.
+// Types are separated by commas or spaces, and anything after ` -- ` is the reason.
+pub fn extract_suppressions_from_contents(contents: &str) -> Vec<Suppression> {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let (next_line, rest) =
+                if let Some(start) = line.find(DISABLE_NEXT_LINE_DIRECTIVE) {
+                    (true, &line[start + DISABLE_NEXT_LINE_DIRECTIVE.len()..])
+                } else {
+                    let start = line.find(DISABLE_DIRECTIVE)?;
+                    (false, &line[start + DISABLE_DIRECTIVE.len()..])
+                };
+            if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+                return None;
+            }
+
+            let (types, reason) = match rest.split_once(" -- ") {
+                Some((types, reason)) => {
+                    (types, Some(reason.trim().to_owned()))
+                }
+                None => (rest, None),
+            };
+            Some(Suppression {
+                line: index + 1,
+                next_line,
+                violation_types: types
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|t| !t.is_empty())
+                    .map(String::from)
+                    .collect(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_extract_sigils_from_contents() {
+        let contents = "\
+# frozen_string_literal: true
+# pack_public: false
+# pack_visible_to: [packs/a, 'packs/b']
+# pack_owner: \"Team A\"
+# pack_public true
+";
+        assert_eq!(
+            extract_sigils_from_contents(contents),
+            vec![
+                Sigil {
+                    name: String::from("public"),
+                    value: SigilValue::Bool(false),
+                },
+                Sigil {
+                    name: String::from("visible_to"),
+                    value: SigilValue::List(vec![
+                        String::from("packs/a"),
+                        String::from("packs/b")
+                    ]),
+                },
+                Sigil {
+                    name: String::from("owner"),
+                    value: SigilValue::String(String::from("Team A")),
+                },
+            ]
+        );
+        assert_eq!(
+            extract_sigils_from_contents("# pack_public: true # legacy\n"),
+            vec![Sigil {
+                name: String::from("public"),
+                value: SigilValue::Bool(true),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_extract_suppressions_from_contents() {
+        let contents = "\
+module Foo
+  # packs:disable-next-line privacy, dependency
+  Bar
+  Baz # packs:disable dependency -- Migrating in #123
+  # packs:disabled privacy
+end
+";
+        assert_eq!(
+            extract_suppressions_from_contents(contents),
+            vec![
+                Suppression {
+                    line: 2,
+                    next_line: true,
+                    violation_types: vec![
+                        String::from("privacy"),
+                        String::from("dependency")
+                    ],
+                    reason: None,
+                },
+                Suppression {
+                    line: 4,
+                    next_line: false,
+                    violation_types: vec![String::from("dependency")],
+                    reason: Some(String::from("Migrating in #123")),
+                },
+            ]
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     fs::File,
     path::{Path, PathBuf},
@@ -10,6 +10,10 @@ use serde::{
     Deserialize, Deserializer, Serialize,
 };
 
+use super::checker::architecture_rule::ArchitectureRule;
+use super::checker::forbidden_constant::ForbiddenConstant;
+use super::configuration::TodoStorage;
+
 const CONFIG_FILE_NAME: &str = "packwerk.yml";
 const PACKS_FIRST_CONFIG_FILE_NAME: &str = "packs.yml";
 
@@ -48,9 +52,19 @@ pub(crate) struct RawConfiguration {
     #[serde(default)]
     pub autoload_paths: Option<Vec<String>>,
 
-    // Architecture layers
+    // Architecture layers, either ordered from highest to lowest or as a map of each
+    // layer to the layers it may depend on
+    #[serde(default)]
+    pub layers: RawLayers,
+
+    // Rules between groups of packs, e.g. that `packs/domain/**` may not depend on
+    // `packs/web/**`
+    #[serde(default)]
+    pub architecture_rules: Vec<ArchitectureRule>,
+
+    // Constants that may not be referenced, with what to use instead
     #[serde(default)]
-    pub layers: Vec<String>,
+    pub forbidden_constants: Vec<ForbiddenConstant>,
 
     // Experimental parser
     #[serde(default)]
@@ -71,6 +85,33 @@ pub(crate) struct RawConfiguration {
     // Use packs copy
     #[serde(default)]
     pub packs_first_mode: bool,
+
+    // How many days before a todo_deadline or an enforcement_globs_ignore `expires_on`
+    // date `check` starts warning about it
+    #[serde(default = "default_expiry_warning_days")]
+    pub expiry_warning_days: u32,
+
+    // Whether recorded violations are stored in each pack or in one file at the root
+    #[serde(default)]
+    pub todo_storage: TodoStorage,
+
+    // Record how many times each file references a recorded constant, so additional
+    // references are reported as new violations
+    #[serde(default)]
+    pub todo_occurrence_counts: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub(crate) enum RawLayers {
+    Ordered(Vec<String>),
+    Graph(BTreeMap<String, Option<Vec<String>>>),
+}
+
+impl Default for RawLayers {
+    fn default() -> Self {
+        RawLayers::Ordered(vec![])
+    }
 }
 
 pub(crate) fn get(absolute_root: &Path) -> anyhow::Result<RawConfiguration> {
@@ -148,6 +189,10 @@ fn default_custom_associations() -> Vec<String> {
     vec![]
 }
 
+fn default_expiry_warning_days() -> u32 {
+    14
+}
+
 fn default_cache() -> bool {
     true
 }
@@ -156,7 +201,9 @@ fn default_cache_directory() -> String {
     String::from("tmp/cache/packwerk")
 }
 
-fn string_or_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+pub(crate) fn string_or_vec<'de, D>(
+    deserializer: D,
+) -> Result<Vec<String>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -11,16 +11,20 @@ use crate::packs::{
     process_files_with_cache, ProcessedFile,
 };
 
-use super::{checker::reference::Reference, Configuration, Sigil};
+use super::{checker::reference::Reference, Configuration, Sigil, Suppression};
 
 // It might be nice to have this return a simpler type rather than the tuple
 // This method returns everything we need as input into packwerk checking
-// (references and sigils). Not sure on naming yet.
+// (references, sigils and suppressions). Not sure on naming yet.
 #[allow(clippy::type_complexity)]
 pub(crate) fn get_all_references_and_sigils(
     configuration: &Configuration,
     absolute_paths: &HashSet<PathBuf>,
-) -> anyhow::Result<(Vec<Reference>, HashMap<PathBuf, Vec<Sigil>>)> {
+) -> anyhow::Result<(
+    Vec<Reference>,
+    HashMap<PathBuf, Vec<Sigil>>,
+    HashMap<PathBuf, Vec<Suppression>>,
+)> {
     let cache = configuration.get_cache();
 
     debug!("Getting unresolved references (using cache if possible)");
@@ -74,6 +78,16 @@ pub(crate) fn get_all_references_and_sigils(
             );
         }
     }
+    let mut path_to_suppressions: HashMap<PathBuf, Vec<Suppression>> =
+        HashMap::new();
+    for processed_file in &processed_files_to_check {
+        if !processed_file.suppressions.is_empty() {
+            path_to_suppressions.insert(
+                processed_file.absolute_path.to_owned(),
+                processed_file.suppressions.to_owned(),
+            );
+        }
+    }
 
     debug!("Turning unresolved references into fully qualified references");
     let references: anyhow::Result<Vec<Reference>> = processed_files_to_check
@@ -105,5 +119,5 @@ pub(crate) fn get_all_references_and_sigils(
         );
     debug!("Finished turning unresolved references into fully qualified references");
 
-    Ok((references?, path_to_sigils))
+    Ok((references?, path_to_sigils, path_to_suppressions))
 }
@@ -0,0 +1,63 @@
+use assert_cmd::prelude::*;
+use serial_test::serial;
+use std::{error::Error, fs, process::Command};
+
+mod common;
+
+const ORDERS_TODO: &str =
+    "tests/fixtures/architecture_rules/packs/domain/orders/package_todo.yml";
+
+#[test]
+#[serial]
+fn test_check_with_architecture_rules() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/architecture_rules")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    // packs/web/admin ignores its references to packs/experiments/beta with
+    // enforcement_globs_ignore, and the rule for packs/experiments only warns
+    assert!(stripped_output.contains("1 violation(s) detected:"));
+    assert!(stripped_output.contains("1 warning(s):"));
+    assert!(stripped_output.contains("packs/domain/orders/app/services/order.rb:3:4\nArchitecture rule violation: `::Admin` belongs to `packs/web/admin`, which cannot be referenced from `packs/domain/orders` because `packs/domain/**` may not depend on `packs/web/**`"));
+    assert!(stripped_output.contains("packs/domain/orders/app/services/order.rb:4:4\nArchitecture rule violation: `::Beta` belongs to `packs/experiments/beta`, which cannot be referenced from `packs/domain/orders` because `packs/experiments/**` may only be referenced by `packs/experiments/**`"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_records_architecture_violations() -> Result<(), Box<dyn Error>> {
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/architecture_rules")
+        .arg("update")
+        .assert()
+        .try_success();
+    let recorded = fs::read_to_string(ORDERS_TODO);
+    let _ = fs::remove_file(ORDERS_TODO);
+
+    result?;
+    let recorded = recorded?;
+    assert!(recorded.ends_with(
+        "---
+packs/web/admin:
+  \"::Admin\":
+    violations:
+    - architecture_rule
+    files:
+    - packs/domain/orders/app/services/order.rb
+"
+    ));
+
+    common::teardown();
+    Ok(())
+}
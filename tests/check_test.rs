@@ -31,6 +31,87 @@ fn test_check() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_check_with_blame() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("check")
+        .arg("--blame")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = stripped_output(output);
+
+    assert!(stripped_output.contains("2 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:3:4\nPrivacy violation: `::Bar` is private to `packs/bar`, but referenced from `packs/foo`\nIntroduced "));
+    assert!(stripped_output.contains("New violations by author:"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_with_markdown_format() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("check")
+        .arg("--format")
+        .arg("markdown")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "<summary><code>packs/foo</code>: 2 new violation(s)</summary>",
+        ))
+        .stdout(predicate::str::contains(
+            "| dependency | `::Bar` | `packs/bar` | [packs/foo/app/services/foo.rb:3](packs/foo/app/services/foo.rb#L3) |",
+        ))
+        .stdout(predicate::str::contains(
+            "| privacy | `::Bar` | `packs/bar` | [packs/foo/app/services/foo.rb:3](packs/foo/app/services/foo.rb#L3) |",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_with_json_format_and_blame() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("check")
+        .arg("--blame")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    let violations = report["violations"].as_array().unwrap();
+    assert_eq!(violations.len(), 2);
+    assert_eq!(violations[0]["file"], "packs/foo/app/services/foo.rb");
+    assert_eq!(violations[0]["line"], 3);
+    assert!(violations[0]["blame"]["author"].is_string());
+    let counts_by_author = report["violations_by_author"].as_array().unwrap();
+    assert_eq!(
+        counts_by_author
+            .iter()
+            .map(|count| count["count"].as_u64().unwrap())
+            .sum::<u64>(),
+        2
+    );
+
+    common::teardown();
+    Ok(())
+}
+
 #[test]
 fn test_check_enforce_privacy_disabled() -> Result<(), Box<dyn Error>> {
     let output = Command::cargo_bin("packs")?
@@ -193,6 +274,32 @@ fn test_check_with_experimental_parser() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_check_ignores_unresolved_constants() -> Result<(), Box<dyn Error>> {
+    // packs/baz doesn't define `Baz`, so neither parser can resolve the reference to
+    // it in foo.rb. The experimental parser reports it as a reference without a
+    // defining pack, which none of the pack checkers enforce.
+    for parser_args in [vec![], vec!["--experimental-parser"]] {
+        let output = Command::cargo_bin("packs")?
+            .arg("--project-root")
+            .arg("tests/fixtures/simple_app")
+            .args(parser_args)
+            .arg("check")
+            .assert()
+            .failure()
+            .get_output()
+            .stdout
+            .clone();
+
+        let stripped_output = stripped_output(output);
+        assert!(stripped_output.contains("2 violation(s) detected:"));
+        assert!(!stripped_output.contains("Baz"));
+    }
+
+    common::teardown();
+    Ok(())
+}
+
 #[test]
 fn test_check_with_stale_violations() -> Result<(), Box<dyn Error>> {
     Command::cargo_bin("packs")
@@ -204,7 +311,37 @@ fn test_check_with_stale_violations() -> Result<(), Box<dyn Error>> {
         .failure()
         .stdout(predicate::str::contains(
             "There were stale violations found, please run `packs update`",
-        ));
+        ))
+        .stdout(predicate::str::contains(
+            "- packs/foo has a recorded dependency violation on `::Bar` (defined in `packs/bar`) in packs/foo/app/services/foo.rb, but the violation was fixed",
+        ))
+        .stdout(predicate::str::contains(
+            "recorded privacy violation on `::Bar`",
+        ).not());
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_files_only_lists_stale_violations_in_those_files(
+) -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")
+        .unwrap()
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("check")
+        .arg("packs/bar/app/services/bar.rb")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "- packs/bar has a recorded dependency violation on `::Foo` (defined in `packs/foo`) in packs/bar/app/services/bar.rb, but the violation was fixed",
+        ))
+        // packs/foo/app/services/foo.rb wasn't checked
+        .stdout(
+            predicate::str::contains("in packs/foo/app/services/foo.rb")
+                .not(),
+        );
 
     common::teardown();
     Ok(())
@@ -222,6 +359,9 @@ fn test_check_with_stale_violations_when_file_no_longer_exists(
         .failure()
         .stdout(predicate::str::contains(
             "There were stale violations found, please run `packs update`",
+        ))
+        .stdout(predicate::str::contains(
+            "- packs/bar has a recorded privacy violation on `::Foo` (defined in `packs/foo`) in packs/bar/app/services/file_was_deleted.rb, but the file was deleted",
         ));
 
     common::teardown();
@@ -20,6 +20,11 @@ pub fn teardown() {
         });
 }
 
+#[allow(dead_code)]
+pub fn stripped_output(output: Vec<u8>) -> String {
+    String::from_utf8_lossy(&strip_ansi_escapes::strip(output)).to_string()
+}
+
 #[allow(dead_code)]
 pub fn delete_foobar() {
     let directory = PathBuf::from("tests/fixtures/simple_app/packs/foobar");
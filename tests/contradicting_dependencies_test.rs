@@ -0,0 +1,44 @@
+use assert_cmd::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+#[test]
+fn test_validate_dependencies_against_checkers() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contradicting_dependencies")
+        .arg("validate")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    assert!(stripped_output.contains("3 validation error(s) detected:"));
+    assert!(stripped_output.contains("Invalid 'dependencies' option in 'packs/utilities/package.yml'. `packs/utilities` (whose layer is `utilities`) cannot depend on `packs/product` (whose layer is `product`)"));
+    assert!(stripped_output.contains("Invalid 'dependencies' option in 'packs/foo/package.yml'. `packs/secret` is not visible to `packs/foo` (none of the visible_to rules in packs/secret/package.yml match: `packs/bar`)"));
+    assert!(stripped_output.contains("Invalid 'dependencies' option in 'packs/foo/package.yml'. `packs/domain/internal` is private to its folder, and `packs/foo` is not a sibling pack or parent pack"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_validate_dependencies_when_checkers_are_disabled(
+) -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contradicting_dependencies")
+        .arg("--disable-enforce-layers")
+        .arg("--disable-enforce-visibility")
+        .arg("--disable-enforce-folder-privacy")
+        .arg("validate")
+        .assert()
+        .success();
+
+    common::teardown();
+    Ok(())
+}
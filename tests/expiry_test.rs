@@ -0,0 +1,29 @@
+use assert_cmd::Command;
+use std::error::Error;
+
+mod common;
+
+#[test]
+fn test_check_with_expired_todo_and_ignore() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/expiring_todo")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    assert!(stripped_output.contains("Warning: packs/foo/package_todo.yml expired on 2000-01-01, so its recorded violations are reported as new violations"));
+    assert!(stripped_output.contains("Warning: The enforcement_globs_ignore entry for dependency in packs/baz/package.yml (reason: legacy code) expired on 2000-01-01 and no longer applies"));
+    assert!(stripped_output.contains("2 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:3:4\nDependency violation: `::Bar` belongs to `packs/bar`, but `packs/foo/package.yml` does not specify a dependency on `packs/bar`."));
+    assert!(stripped_output.contains("packs/baz/app/services/baz.rb:3:4\nDependency violation: `::Bar` belongs to `packs/bar`, but `packs/baz/package.yml` does not specify a dependency on `packs/bar`."));
+    assert!(!stripped_output.contains("packs/qux"));
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use assert_cmd::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+fn assert_file_sigils_apply(stripped_output: &str) {
+    assert!(stripped_output.contains("2 violation(s) detected:"));
+    // `# pack_public: false` makes a file in the public folder private
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:3:4\nPrivacy violation: `::Bar` is private to `packs/bar`, but referenced from `packs/foo`"));
+    // `# pack_visible_to: [packs/baz]` replaces the pack's visible_to
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:7:4\nVisibility violation: `::Shared` belongs to `packs/bar`, which is not visible to `packs/foo`"));
+    assert!(!stripped_output.contains("not visible to `packs/baz`"));
+}
+
+#[test]
+fn test_check_with_file_sigils() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/file_sigils")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert_file_sigils_apply(&common::stripped_output(output));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_single_file_with_file_sigils() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/file_sigils")
+        .arg("check")
+        .arg("packs/foo/app/services/foo.rb")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert_file_sigils_apply(&common::stripped_output(output));
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,6 @@
+class Order
+  def self.call
+    Admin.call
+    Beta.call
+  end
+end
@@ -0,0 +1,5 @@
+class Alpha
+  def self.call
+    Beta.call
+  end
+end
@@ -0,0 +1,3 @@
+class Beta
+  def self.call; end
+end
@@ -0,0 +1,6 @@
+class Admin
+  def self.call
+    Order.call
+    Beta.call
+  end
+end
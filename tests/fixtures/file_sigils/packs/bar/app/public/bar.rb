@@ -0,0 +1,4 @@
+# pack_public: false
+
+module Bar
+end
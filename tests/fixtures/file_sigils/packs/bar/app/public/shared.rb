@@ -0,0 +1,4 @@
+# pack_visible_to: [packs/baz]
+
+module Shared
+end
@@ -0,0 +1,5 @@
+module Baz
+  def calls_shared
+    Shared
+  end
+end
@@ -0,0 +1,9 @@
+module Foo
+  def calls_bar
+    Bar
+  end
+
+  def calls_shared
+    Shared
+  end
+end
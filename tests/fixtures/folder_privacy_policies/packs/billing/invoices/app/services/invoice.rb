@@ -0,0 +1,5 @@
+class Invoice
+  def call
+    Refund.issue
+  end
+end
@@ -0,0 +1,5 @@
+class Checkout
+  def call
+    Refund.issue
+  end
+end
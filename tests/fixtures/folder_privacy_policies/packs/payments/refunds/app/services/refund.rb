@@ -0,0 +1,3 @@
+class Refund
+  def self.issue; end
+end
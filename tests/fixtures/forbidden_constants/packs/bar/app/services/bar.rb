@@ -0,0 +1,5 @@
+class Bar
+  def call
+    Legacy.charge
+  end
+end
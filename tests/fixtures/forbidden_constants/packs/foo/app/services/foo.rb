@@ -0,0 +1,6 @@
+class Foo
+  def call
+    Net::HTTP.get(URI("https://example.com"))
+    Legacy.charge
+  end
+end
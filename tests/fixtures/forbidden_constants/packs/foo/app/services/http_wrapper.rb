@@ -0,0 +1,5 @@
+class HttpWrapper
+  def get(uri)
+    Net::HTTP.get(uri)
+  end
+end
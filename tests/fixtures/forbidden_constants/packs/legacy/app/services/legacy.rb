@@ -0,0 +1,3 @@
+module Legacy
+  def self.charge; end
+end
@@ -0,0 +1,3 @@
+class ConnectionPool
+  def with; end
+end
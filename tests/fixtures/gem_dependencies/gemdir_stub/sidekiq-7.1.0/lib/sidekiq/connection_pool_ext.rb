@@ -0,0 +1,3 @@
+class ConnectionPool
+  def sidekiq_stats; end
+end
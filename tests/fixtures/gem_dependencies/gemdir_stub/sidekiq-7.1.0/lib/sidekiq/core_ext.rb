@@ -0,0 +1,3 @@
+class String
+  def constantize_job; end
+end
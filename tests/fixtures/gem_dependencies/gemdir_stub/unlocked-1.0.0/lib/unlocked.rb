@@ -0,0 +1,3 @@
+class Unlocked
+  def call; end
+end
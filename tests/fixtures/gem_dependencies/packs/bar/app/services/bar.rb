@@ -0,0 +1,5 @@
+class Bar
+  def call
+    Sidekiq::Client.new.push
+  end
+end
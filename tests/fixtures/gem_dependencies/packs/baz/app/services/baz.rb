@@ -0,0 +1,5 @@
+class Baz
+  def call
+    Sidekiq::Job.perform_async
+  end
+end
@@ -0,0 +1,8 @@
+class Foo
+  def call
+    Sidekiq::Client.new.push
+    ConnectionPool.new.with
+    Unlocked.new.call
+    String.new.constantize_job
+  end
+end
@@ -0,0 +1,3 @@
+class String
+  def to_s; end
+end
@@ -0,0 +1,20 @@
+module Foo
+  def calls_bar
+    # packs:disable-next-line dependency, privacy
+    Bar
+  end
+
+  def calls_bar_again
+    Bar # packs:disable dependency -- Migrating in #123
+  end
+
+  def calls_baz
+    # packs:disable-next-line privacy
+    Baz
+  end
+
+  def calls_baz_again
+    # packs:disable-next-line
+    Baz
+  end
+end
@@ -0,0 +1,5 @@
+class Checkout
+  def self.call
+    Search.call
+  end
+end
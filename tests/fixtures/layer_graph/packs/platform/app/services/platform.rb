@@ -0,0 +1,5 @@
+class Platform
+  def self.call
+    nil
+  end
+end
@@ -0,0 +1,5 @@
+class Search
+  def self.call
+    Platform.call
+  end
+end
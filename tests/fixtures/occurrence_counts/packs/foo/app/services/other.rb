@@ -0,0 +1,7 @@
+module Foo
+  class Other
+    def bars
+      [Bar, Bar]
+    end
+  end
+end
@@ -0,0 +1,7 @@
+module Bar
+  module Internal
+    class Config
+      def call; end
+    end
+  end
+end
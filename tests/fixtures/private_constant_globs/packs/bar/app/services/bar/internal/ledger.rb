@@ -0,0 +1,7 @@
+module Bar
+  module Internal
+    class Ledger
+      def call; end
+    end
+  end
+end
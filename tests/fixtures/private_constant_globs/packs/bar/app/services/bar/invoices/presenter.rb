@@ -0,0 +1,7 @@
+module Bar
+  module Invoices
+    class Presenter
+      def call; end
+    end
+  end
+end
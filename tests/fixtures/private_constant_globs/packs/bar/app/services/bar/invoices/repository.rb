@@ -0,0 +1,7 @@
+module Bar
+  module Invoices
+    class Repository
+      def call; end
+    end
+  end
+end
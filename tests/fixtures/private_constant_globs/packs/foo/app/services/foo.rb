@@ -0,0 +1,8 @@
+class Foo
+  def call
+    Bar::Internal::Ledger
+    Bar::Internal::Config
+    Bar::Invoices::Repository
+    Bar::Invoices::Presenter
+  end
+end
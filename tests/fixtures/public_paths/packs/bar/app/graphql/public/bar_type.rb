@@ -0,0 +1,4 @@
+module Public
+  class BarType
+  end
+end
@@ -0,0 +1,4 @@
+module Internal
+  class Secret
+  end
+end
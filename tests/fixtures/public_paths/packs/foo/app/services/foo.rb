@@ -0,0 +1,11 @@
+module Foo
+  def calls_public_constants
+    Bar
+    Public::BarType
+  end
+
+  def calls_private_constants
+    Internal::Secret
+    Hidden
+  end
+end
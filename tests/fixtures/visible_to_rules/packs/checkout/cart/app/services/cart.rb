@@ -0,0 +1,5 @@
+class Cart
+  def call
+    Payments.charge
+  end
+end
@@ -0,0 +1,5 @@
+class Ledger
+  def call
+    Payments.charge
+  end
+end
@@ -0,0 +1,6 @@
+# pack_owner: Finance
+class FinanceReport
+  def call
+    Payments.charge
+  end
+end
@@ -0,0 +1,5 @@
+class Marketing
+  def call
+    Payments.charge
+  end
+end
@@ -0,0 +1,3 @@
+class Payments
+  def self.charge; end
+end
@@ -0,0 +1,5 @@
+class Refunds
+  def call
+    Payments.charge
+  end
+end
@@ -0,0 +1,26 @@
+use assert_cmd::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+#[test]
+fn test_check_with_same_domain_policy() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/folder_privacy_policies")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    // packs/payments/checkout is in the same domain folder as packs/payments/refunds
+    assert!(stripped_output.contains("1 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/billing/invoices/app/services/invoice.rb:3:4\nFolder Privacy violation: `::Refund` belongs to `packs/payments/refunds`, which is private to `packs/billing/invoices` as it is not in the domain folder `packs/payments`."));
+
+    common::teardown();
+    Ok(())
+}
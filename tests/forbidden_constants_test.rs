@@ -0,0 +1,105 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serial_test::serial;
+use std::{error::Error, fs, process::Command};
+
+mod common;
+
+const FOO_TODO: &str =
+    "tests/fixtures/forbidden_constants/packs/foo/package_todo.yml";
+
+fn assert_forbidden_constants_detected(stripped_output: &str) {
+    // packs/bar can reference ::Legacy, since it is only forbidden in packs/foo, and
+    // packs/foo ignores http_wrapper.rb with enforcement_globs_ignore
+    assert!(stripped_output.contains("2 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:3:4\nForbidden constant violation: `Net::HTTP` cannot be referenced from `packs/foo`. Use ::HttpClient instead."));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:4:4\nForbidden constant violation: `::Legacy` cannot be referenced from `packs/foo`. Use ::Billing instead."));
+}
+
+#[test]
+#[serial]
+fn test_check_with_forbidden_constants() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/forbidden_constants")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert_forbidden_constants_detected(&common::stripped_output(output));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_check_with_forbidden_constants_and_experimental_parser(
+) -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/forbidden_constants")
+        .arg("--experimental-parser")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert_forbidden_constants_detected(&common::stripped_output(output));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_records_forbidden_constants() -> Result<(), Box<dyn Error>> {
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/forbidden_constants")
+        .arg("update")
+        .assert()
+        .try_success();
+    let recorded = fs::read_to_string(FOO_TODO);
+    let _ = fs::remove_file(FOO_TODO);
+
+    // ::Legacy is forbidden with `enforcement: strict`, so it isn't recorded
+    result?.stdout(predicate::str::contains(
+        "packs/foo cannot have forbidden_constant violations on packs/legacy because strict mode is enabled for forbidden_constant violations in the matching forbidden_constants entry",
+    ));
+    // Constants that aren't defined in a pack are recorded under the root pack
+    assert!(recorded?.ends_with(
+        "---
+\".\":
+  \"Net::HTTP\":
+    violations:
+    - forbidden_constant
+    files:
+    - packs/foo/app/services/foo.rb
+"
+    ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_validate_forbidden_constants() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/forbidden_constants")
+        .arg("validate")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Invalid 'packs' option in forbidden_constants for `::Legacy`. `packs/removed` does not match any pack",
+        ));
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,225 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serial_test::serial;
+use std::{error::Error, fs, process::Command};
+
+mod common;
+
+const GEMDIR: &str = "--gemdir=tests/fixtures/gem_dependencies/gemdir_stub";
+const RUBYDIR: &str = "--rubydir=tests/fixtures/gem_dependencies/rubydir_stub";
+const FOO_TODO: &str =
+    "tests/fixtures/gem_dependencies/packs/foo/package_todo.yml";
+
+fn assert_gem_dependency_violations_detected(stripped_output: &str) {
+    // packs/bar doesn't enforce gem_dependencies, packs/baz declares sidekiq, and
+    // packs/foo declares connection_pool, one of the gems defining `ConnectionPool`
+    assert!(stripped_output.contains("1 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:3:4\nGem dependency violation: `Sidekiq::Client` belongs to gem `sidekiq`, which is not a gem_dependency of `packs/foo`"));
+    // sidekiq reopens String, which Ruby defines
+    assert!(!stripped_output.contains("`String`"));
+}
+
+#[test]
+#[serial]
+fn test_check_with_gem_dependencies() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/gem_dependencies")
+        .arg(GEMDIR)
+        .arg(RUBYDIR)
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert_gem_dependency_violations_detected(&common::stripped_output(output));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_check_with_gem_dependencies_and_experimental_parser(
+) -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/gem_dependencies")
+        .arg(GEMDIR)
+        .arg(RUBYDIR)
+        .arg("--experimental-parser")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    assert_gem_dependency_violations_detected(&common::stripped_output(output));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_records_gem_dependency_violations() -> Result<(), Box<dyn Error>>
+{
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/gem_dependencies")
+        .arg(GEMDIR)
+        .arg(RUBYDIR)
+        .arg("update")
+        .assert()
+        .try_success();
+    let recorded = fs::read_to_string(FOO_TODO);
+    let check_result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/gem_dependencies")
+        .arg(GEMDIR)
+        .arg(RUBYDIR)
+        .arg("check")
+        .assert()
+        .try_success();
+    let _ = fs::remove_file(FOO_TODO);
+
+    result?;
+    check_result?;
+    // Gem constants are recorded under the root pack
+    assert!(recorded?.ends_with(
+        "---
+\".\":
+  \"Sidekiq::Client\":
+    violations:
+    - gem_dependency
+    files:
+    - packs/foo/app/services/foo.rb
+"
+    ));
+
+    common::teardown();
+    Ok(())
+}
+
+const ROOT_RECORDED_TODO: &str = "\
+# This file contains a list of dependencies that are not part of the long term plan for the
+# 'packs/foo' package.
+# We should generally work to reduce this list over time.
+#
+# You can regenerate this file using the following command:
+#
+# pks update
+---
+\".\":
+  \"ConnectionPool\":
+    violations:
+    - gem_dependency
+    files:
+    - packs/foo/app/services/foo.rb
+  \"Sidekiq::Client\":
+    violations:
+    - gem_dependency
+    files:
+    - packs/foo/app/services/foo.rb
+";
+
+// Gem constants are recorded under the root pack, "."
+#[test]
+#[serial]
+fn test_todo_entries_recorded_under_the_root_pack() -> Result<(), Box<dyn Error>>
+{
+    let merge_dir = "tests/fixtures/gem_dependencies/tmp/merge";
+    fs::create_dir_all(merge_dir)?;
+    let ancestor = format!("{}/ancestor.yml", merge_dir);
+    let other = format!("{}/other.yml", merge_dir);
+    fs::write(&ancestor, "")?;
+    fs::write(&other, ROOT_RECORDED_TODO)?;
+    fs::write(
+        FOO_TODO,
+        ROOT_RECORDED_TODO.replace(
+            "  \"ConnectionPool\":
+    violations:
+    - gem_dependency
+    files:
+    - packs/foo/app/services/foo.rb
+",
+            "",
+        ),
+    )?;
+
+    let merge_result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/gem_dependencies")
+        .arg(GEMDIR)
+        .arg(RUBYDIR)
+        .arg("merge-todo")
+        .arg(&ancestor)
+        .arg(FOO_TODO)
+        .arg(&other)
+        .assert()
+        .try_success();
+    let merged = fs::read_to_string(FOO_TODO);
+    let check_output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/gem_dependencies")
+        .arg(GEMDIR)
+        .arg(RUBYDIR)
+        .arg("check")
+        .output()?
+        .stdout;
+    let update_result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/gem_dependencies")
+        .arg(GEMDIR)
+        .arg(RUBYDIR)
+        .arg("update")
+        .assert()
+        .try_success();
+    let updated = fs::read_to_string(FOO_TODO);
+    let _ = fs::remove_file(FOO_TODO);
+    fs::remove_dir_all(merge_dir)?;
+
+    merge_result?;
+    assert_eq!(merged?, ROOT_RECORDED_TODO);
+
+    // Sidekiq::Client is recorded, and foo.rb no longer references ConnectionPool
+    let stripped_output = common::stripped_output(check_output);
+    assert!(!stripped_output.contains("violation(s) detected"));
+    assert!(stripped_output.contains("packs/foo has a recorded gem_dependency violation on `ConnectionPool` (defined in `.`) in packs/foo/app/services/foo.rb, but the violation was fixed"));
+
+    update_result?;
+    assert!(updated?.ends_with(
+        "---
+\".\":
+  \"Sidekiq::Client\":
+    violations:
+    - gem_dependency
+    files:
+    - packs/foo/app/services/foo.rb
+"
+    ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_validate_gem_dependencies() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/gem_dependencies")
+        .arg(GEMDIR)
+        .arg(RUBYDIR)
+        .arg("validate")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Invalid 'gem_dependencies' option in 'packs/baz/package.yml'. `redis` is not in Gemfile.lock",
+        ));
+
+    common::teardown();
+    Ok(())
+}
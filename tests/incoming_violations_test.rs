@@ -0,0 +1,104 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serial_test::serial;
+use std::{error::Error, fs, path::Path, process::Command};
+
+mod common;
+
+const BAR_TODO: &str =
+    "tests/fixtures/incoming_violations/packs/bar/package_todo.yml";
+
+#[test]
+#[serial]
+fn test_check_with_incoming_violations() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/incoming_violations")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/services/other_foo.rb",
+        ))
+        .stdout(predicate::str::contains("stale").not());
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_with_incoming_violations() -> Result<(), Box<dyn Error>> {
+    let original = fs::read_to_string(BAR_TODO)?;
+
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/incoming_violations")
+        .arg("update")
+        .assert()
+        .try_success();
+    let updated = fs::read_to_string(BAR_TODO)?;
+    fs::write(BAR_TODO, &original)?;
+
+    result?;
+    assert_eq!(
+        updated,
+        original
+            .replace(
+                "    - packs/foo/app/services/foo.rb\n",
+                "    - packs/foo/app/services/foo.rb\n    - packs/foo/app/services/other_foo.rb\n",
+            )
+            .replace(
+                "      packs/foo/app/services/foo.rb: packs/foo\n",
+                "      packs/foo/app/services/foo.rb: packs/foo\n      packs/foo/app/services/other_foo.rb: packs/foo\n",
+            )
+    );
+    assert!(!Path::new(
+        "tests/fixtures/incoming_violations/packs/foo/package_todo.yml"
+    )
+    .exists());
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_scoped_update_with_incoming_violations() -> Result<(), Box<dyn Error>> {
+    let original = fs::read_to_string(BAR_TODO)?;
+
+    for scope in [
+        vec!["--pack", "packs/foo"],
+        vec!["packs/foo/app/services/other_foo.rb"],
+    ] {
+        let result = Command::cargo_bin("pks")?
+            .arg("--project-root")
+            .arg("tests/fixtures/incoming_violations")
+            .arg("update")
+            .args(&scope)
+            .assert()
+            .try_success();
+        let updated = fs::read_to_string(BAR_TODO)?;
+        fs::write(BAR_TODO, &original)?;
+
+        result?;
+        assert_eq!(
+            updated,
+            original
+                .replace(
+                    "    - packs/foo/app/services/foo.rb\n",
+                    "    - packs/foo/app/services/foo.rb\n    - packs/foo/app/services/other_foo.rb\n",
+                )
+                .replace(
+                    "      packs/foo/app/services/foo.rb: packs/foo\n",
+                    "      packs/foo/app/services/foo.rb: packs/foo\n      packs/foo/app/services/other_foo.rb: packs/foo\n",
+                ),
+            "update {}",
+            scope.join(" ")
+        );
+    }
+
+    common::teardown();
+    Ok(())
+}
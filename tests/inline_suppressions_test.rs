@@ -0,0 +1,67 @@
+use assert_cmd::prelude::*;
+use serial_test::serial;
+use std::{error::Error, fs, process::Command};
+
+mod common;
+
+const FOO_TODO: &str =
+    "tests/fixtures/inline_suppressions/packs/foo/package_todo.yml";
+
+#[test]
+#[serial]
+fn test_check_with_inline_suppressions() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/inline_suppressions")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    assert!(stripped_output.contains("3 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:8:4\nPrivacy violation: `::Bar` is private to `packs/bar`"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:13:4\nDependency violation: `::Baz` belongs to `packs/baz`"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:18:4\nDependency violation: `::Baz` belongs to `packs/baz`"));
+    assert!(stripped_output.contains("There were unused suppressions found, please remove them\n- packs/foo/app/services/foo.rb:12 suppresses privacy violations, but there are none\n- packs/foo/app/services/foo.rb:17 names no violation types, so it suppresses nothing"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_skips_suppressed_violations() -> Result<(), Box<dyn Error>> {
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/inline_suppressions")
+        .arg("update")
+        .assert()
+        .try_success();
+    let recorded = fs::read_to_string(FOO_TODO);
+    let _ = fs::remove_file(FOO_TODO);
+
+    result?;
+    assert!(recorded?.ends_with(
+        "---
+packs/bar:
+  \"::Bar\":
+    violations:
+    - privacy
+    files:
+    - packs/foo/app/services/foo.rb
+packs/baz:
+  \"::Baz\":
+    violations:
+    - dependency
+    files:
+    - packs/foo/app/services/foo.rb
+"
+    ));
+
+    common::teardown();
+    Ok(())
+}
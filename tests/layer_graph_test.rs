@@ -0,0 +1,27 @@
+use assert_cmd::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+#[test]
+fn test_check_with_layer_graph() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/layer_graph")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    // packs/search can use packs/platform, but the sibling feature layers can't
+    // reference each other
+    assert!(stripped_output.contains("1 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/checkout/app/services/checkout.rb:3:4\nLayer violation: `::Search` belongs to `packs/search` (whose layer is `search`) cannot be accessed from `packs/checkout` (whose layer is `checkout`), as `checkout` can only depend on `platform`"));
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use assert_cmd::prelude::*;
+use pretty_assertions::assert_eq;
+use std::{error::Error, fs, path::PathBuf, process::Command};
+
+mod common;
+
+const HEADER: &str = "\
+# This file contains a list of dependencies that are not part of the long term plan for the
+# 'packs/foo' package.
+# We should generally work to reduce this list over time.
+#
+# You can regenerate this file using the following command:
+#
+# bin/packwerk update-todo
+---
+";
+
+fn package_todo(groups: &[(&str, &[&str])]) -> String {
+    let mut package_todo = format!("{}packs/bar:\n", HEADER);
+    for (constant_name, files) in groups {
+        package_todo += &format!(
+            "  \"{}\":\n    violations:\n    - dependency\n    files:\n",
+            constant_name
+        );
+        for file in *files {
+            package_todo += &format!("    - {}\n", file);
+        }
+    }
+    package_todo
+}
+
+#[test]
+fn test_merge_todo() -> Result<(), Box<dyn Error>> {
+    let dir = PathBuf::from("tests/fixtures/contains_package_todo/tmp/merge");
+    fs::create_dir_all(&dir)?;
+    let ancestor = dir.join("ancestor.yml");
+    let current = dir.join("current.yml");
+    let other = dir.join("other.yml");
+    fs::write(
+        &ancestor,
+        package_todo(&[(
+            "::Bar",
+            &[
+                "packs/foo/app/services/foo.rb",
+                "packs/foo/app/services/other_foo.rb",
+            ],
+        )]),
+    )?;
+    // The violation in other_foo.rb was fixed on the current side, so it stays removed
+    fs::write(
+        &current,
+        package_todo(&[("::Bar", &["packs/foo/app/services/foo.rb"])]),
+    )?;
+    // The other side added violations, one of them in a file that no longer exists
+    fs::write(
+        &other,
+        package_todo(&[
+            (
+                "::Bar",
+                &[
+                    "packs/foo/app/services/deleted.rb",
+                    "packs/foo/app/services/foo.rb",
+                    "packs/foo/app/services/other_foo.rb",
+                ],
+            ),
+            ("::Baz", &["packs/foo/app/services/other_foo.rb"]),
+        ]),
+    )?;
+
+    let result = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_package_todo")
+        .arg("merge-todo")
+        .arg(&ancestor)
+        .arg(&current)
+        .arg(&other)
+        .assert()
+        .try_success();
+    let merged = fs::read_to_string(&current)?;
+    fs::remove_dir_all(&dir)?;
+
+    result?;
+    assert_eq!(
+        merged,
+        package_todo(&[
+            ("::Bar", &["packs/foo/app/services/foo.rb"]),
+            ("::Baz", &["packs/foo/app/services/other_foo.rb"]),
+        ])
+    );
+
+    common::teardown();
+    Ok(())
+}
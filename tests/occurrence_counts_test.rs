@@ -0,0 +1,131 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serial_test::serial;
+use std::{error::Error, fs, process::Command};
+
+mod common;
+
+const FOO_TODO: &str =
+    "tests/fixtures/occurrence_counts/packs/foo/package_todo.yml";
+
+#[test]
+#[serial]
+fn test_check_reports_references_beyond_recorded_count(
+) -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/occurrence_counts")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    assert!(stripped_output.contains("1 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:7:4\nDependency violation: `::Bar` belongs to `packs/bar`"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_dry_run_with_occurrence_counts() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/occurrence_counts")
+        .arg("update")
+        .arg("--dry-run")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "~ dependency `::Bar` (defined in `packs/bar`) in packs/foo/app/services/foo.rb: 1 -> 2 occurrence(s)",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_records_occurrence_counts() -> Result<(), Box<dyn Error>> {
+    let original = fs::read_to_string(FOO_TODO)?;
+
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/occurrence_counts")
+        .arg("update")
+        .assert()
+        .try_success();
+    let updated = fs::read_to_string(FOO_TODO)?;
+    fs::write(FOO_TODO, &original)?;
+
+    result?;
+    assert_eq!(
+        updated,
+        original.replace(
+            "packs/foo/app/services/foo.rb: 1",
+            "packs/foo/app/services/foo.rb: 2"
+        )
+    );
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_scoped_to_files_keeps_recorded_occurrence_counts(
+) -> Result<(), Box<dyn Error>> {
+    let original = fs::read_to_string(FOO_TODO)?;
+
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/occurrence_counts")
+        .arg("update")
+        .arg("packs/foo/app/services/foo.rb")
+        .assert()
+        .try_success();
+    let updated = fs::read_to_string(FOO_TODO)?;
+    fs::write(FOO_TODO, &original)?;
+
+    result?;
+    // other.rb isn't updated, so its recorded count is kept as is
+    assert_eq!(
+        updated,
+        original.replace(
+            "packs/foo/app/services/foo.rb: 1",
+            "packs/foo/app/services/foo.rb: 2"
+        )
+    );
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_prune_only_keeps_recorded_occurrence_counts(
+) -> Result<(), Box<dyn Error>> {
+    let original = fs::read_to_string(FOO_TODO)?;
+
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/occurrence_counts")
+        .arg("update")
+        .arg("--prune-only")
+        .assert()
+        .try_success();
+    let updated = fs::read_to_string(FOO_TODO)?;
+    fs::write(FOO_TODO, &original)?;
+
+    result?;
+    // The extra reference in foo.rb is a new violation, so its count isn't raised
+    assert_eq!(updated, original);
+
+    common::teardown();
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serial_test::serial;
+use std::{error::Error, fs, process::Command};
+
+mod common;
+
+const BAR_PACKAGE_YML: &str =
+    "tests/fixtures/private_constant_globs/packs/bar/package.yml";
+
+#[test]
+#[serial]
+fn test_check_with_private_constant_globs() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/private_constant_globs")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    assert!(stripped_output.contains("2 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:3:4\nPrivacy violation: `::Bar::Internal::Ledger` is private to `packs/bar`, but referenced from `packs/foo`"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:5:4\nPrivacy violation: `::Bar::Invoices::Repository` is private to `packs/bar`, but referenced from `packs/foo`"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_validate_with_private_constant_globs() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/private_constant_globs")
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Packwerk validate succeeded!"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_validate_with_stale_private_constants() -> Result<(), Box<dyn Error>> {
+    let original = fs::read_to_string(BAR_PACKAGE_YML)?;
+    fs::write(
+        BAR_PACKAGE_YML,
+        original
+            .replace("::Bar::Internal::*", "::Bar::Secret::*")
+            .replace("::Bar::Internal::Config", "::Bar::Internal::Settings"),
+    )?;
+
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/private_constant_globs")
+        .arg("validate")
+        .assert()
+        .try_failure();
+    fs::write(BAR_PACKAGE_YML, &original)?;
+
+    let output = common::stripped_output(result?.get_output().stdout.clone());
+    assert!(output.contains("2 validation error(s) detected:"));
+    assert!(output.contains("`::Bar::Secret::*` in the private_constants of packs/bar/package.yml does not match any constant defined in packs/bar"));
+    assert!(output.contains("`::Bar::Internal::Settings` in the ignored_private_constants of packs/bar/package.yml does not match any constant defined in packs/bar"));
+
+    common::teardown();
+    Ok(())
+}
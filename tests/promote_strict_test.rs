@@ -0,0 +1,130 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use pretty_assertions::assert_eq;
+use serial_test::serial;
+use std::{error::Error, fs, process::Command};
+
+mod common;
+
+const FOO_PACKAGE_YML: &str =
+    "tests/fixtures/contains_stale_violations/packs/foo/package.yml";
+
+#[test]
+#[serial]
+fn test_promote_strict_without_clean_packs() -> Result<(), Box<dyn Error>> {
+    common::set_up_fixtures();
+    let original = fs::read_to_string(FOO_PACKAGE_YML)?;
+
+    // packs/foo still records a (stale) dependency violation until updated
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("promote-strict")
+        .assert()
+        .try_success()
+        .and_then(|assert| {
+            assert.try_stdout(predicate::str::contains(
+                "No packs can be promoted to strict",
+            ))
+        });
+    let updated = fs::read_to_string(FOO_PACKAGE_YML)?;
+    fs::write(FOO_PACKAGE_YML, &original)?;
+
+    result?;
+    assert_eq!(updated, original);
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_with_promote_strict() -> Result<(), Box<dyn Error>> {
+    common::set_up_fixtures();
+    let original = fs::read_to_string(FOO_PACKAGE_YML)?;
+
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("update")
+        .arg("--promote-strict")
+        .assert()
+        .try_success();
+    let updated = fs::read_to_string(FOO_PACKAGE_YML)?;
+    fs::write(FOO_PACKAGE_YML, &original)?;
+    common::set_up_fixtures();
+
+    let stdout = String::from_utf8(result?.get_output().stdout.clone())?;
+    // packs/foo's only recorded dependency violation was stale, so it is
+    // promoted. packs/bar still has a privacy violation.
+    assert_eq!(stdout.matches("Promoted").count(), 1);
+    assert!(
+        stdout.contains("Promoted enforce_dependencies in packs/foo to strict")
+    );
+    assert_eq!(
+        updated,
+        "enforce_dependencies: strict\ndependencies:\n- packs/bar\n"
+    );
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_update_with_promote_strict_and_pack() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("update")
+        .arg("--promote-strict")
+        .arg("--pack")
+        .arg("packs/foo")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--promote-strict cannot be combined with --pack or files",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_with_promote_strict_and_lowered_budget(
+) -> Result<(), Box<dyn Error>> {
+    common::set_up_fixtures();
+    let original = fs::read_to_string(FOO_PACKAGE_YML)?;
+    fs::write(
+        FOO_PACKAGE_YML,
+        format!(
+            "{}violation_budget:\n  dependency: 5\n  privacy: 3\n",
+            original
+        ),
+    )?;
+
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("update")
+        .arg("--promote-strict")
+        .assert()
+        .try_success();
+    let updated = fs::read_to_string(FOO_PACKAGE_YML)?;
+    fs::write(FOO_PACKAGE_YML, &original)?;
+    common::set_up_fixtures();
+
+    let stdout = String::from_utf8(result?.get_output().stdout.clone())?;
+    assert!(stdout.contains("Lowered the violation_budget of packs/foo"));
+    assert!(
+        stdout.contains("Promoted enforce_dependencies in packs/foo to strict")
+    );
+    // Both changes are written to package.yml
+    assert_eq!(
+        updated,
+        "enforce_dependencies: strict\ndependencies:\n- packs/bar\nviolation_budget:\n  dependency: 0\n  privacy: 1\n"
+    );
+
+    common::teardown();
+    Ok(())
+}
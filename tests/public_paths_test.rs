@@ -0,0 +1,45 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+#[test]
+fn test_check_with_public_paths() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/public_paths")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    assert!(stripped_output.contains("2 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:8:4\nPrivacy violation: `::Internal::Secret` is private to `packs/bar`"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:9:4\nPrivacy violation: `::Hidden` is private to `packs/bar`"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_validate_warns_about_unmatched_public_paths(
+) -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/public_paths")
+        .arg("validate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Warning: The public_paths entry `app/missing` in packs/bar/package.yml does not match any files",
+        ))
+        .stdout(predicate::str::contains("app/graphql/public").not());
+
+    common::teardown();
+    Ok(())
+}
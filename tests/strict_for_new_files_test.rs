@@ -0,0 +1,89 @@
+use assert_cmd::prelude::*;
+use pretty_assertions::assert_eq;
+use serial_test::serial;
+use std::{error::Error, fs, process::Command, process::Output};
+
+mod common;
+
+const FIXTURE: &str = "tests/fixtures/contains_package_todo";
+const FOO_PACKAGE_YML: &str =
+    "tests/fixtures/contains_package_todo/packs/foo/package.yml";
+const BAR_PACKAGE_YML: &str =
+    "tests/fixtures/contains_package_todo/packs/bar/package.yml";
+const FOO_TODO: &str =
+    "tests/fixtures/contains_package_todo/packs/foo/package_todo.yml";
+const NEW_FOO: &str =
+    "tests/fixtures/contains_package_todo/packs/foo/app/services/new_foo.rb";
+
+// Runs `pks` against contains_package_todo with both checkers set to
+// `strict_for_new_files` and a file that isn't recorded yet, restoring the
+// fixture afterwards. Only dependency violations are recorded in its todo.
+fn run_with_strict_for_new_files(
+    args: &[&str],
+) -> Result<(Output, String), Box<dyn Error>> {
+    let foo_package_yml = fs::read_to_string(FOO_PACKAGE_YML)?;
+    let bar_package_yml = fs::read_to_string(BAR_PACKAGE_YML)?;
+    let foo_todo = fs::read_to_string(FOO_TODO)?;
+    fs::write(
+        FOO_PACKAGE_YML,
+        "enforce_dependencies: strict_for_new_files\n",
+    )?;
+    fs::write(BAR_PACKAGE_YML, "enforce_privacy: strict_for_new_files\n")?;
+    fs::write(
+        NEW_FOO,
+        "module NewFoo\n  def calls_bar\n    Bar\n  end\nend\n",
+    )?;
+
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg(FIXTURE)
+        .args(args)
+        .output();
+    let updated_todo = fs::read_to_string(FOO_TODO);
+    fs::write(FOO_PACKAGE_YML, foo_package_yml)?;
+    fs::write(BAR_PACKAGE_YML, bar_package_yml)?;
+    fs::write(FOO_TODO, foo_todo)?;
+    fs::remove_file(NEW_FOO)?;
+
+    Ok((output?, updated_todo?))
+}
+
+#[test]
+#[serial]
+fn test_check_with_strict_for_new_files() -> Result<(), Box<dyn Error>> {
+    let (output, _) = run_with_strict_for_new_files(&["check"])?;
+    assert!(!output.status.success());
+
+    let stripped_output = common::stripped_output(output.stdout);
+
+    assert!(stripped_output.contains("4 violation(s) detected:"));
+    // foo.rb and other_foo.rb are already recorded, so their unrecorded
+    // privacy violations are not strict
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:3:4\nPrivacy violation: `::Bar` is private to `packs/bar`"));
+    assert!(stripped_output.contains("packs/foo/app/services/other_foo.rb:3:4\nPrivacy violation: `::Bar` is private to `packs/bar`"));
+    assert!(stripped_output.contains("packs/foo/app/services/new_foo.rb:3:4\nDependency violation: `::Bar` belongs to `packs/bar`"));
+    assert!(stripped_output.contains("packs/foo/app/services/new_foo.rb:3:4\nPrivacy violation: `::Bar` is private to `packs/bar`"));
+    assert_eq!(stripped_output.matches("strict mode is enabled").count(), 2);
+    assert!(stripped_output.contains("packs/foo cannot have dependency violations on packs/bar because strict mode is enabled for dependency violations in the enforcing pack's package.yml file"));
+    assert!(stripped_output.contains("packs/foo cannot have privacy violations on packs/bar because strict mode is enabled for privacy violations in the enforcing pack's package.yml file"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_with_strict_for_new_files() -> Result<(), Box<dyn Error>> {
+    let (output, updated_todo) = run_with_strict_for_new_files(&["update"])?;
+    assert!(output.status.success());
+
+    let stripped_output = common::stripped_output(output.stdout);
+    assert!(stripped_output.contains("2 strict mode violation(s) detected."));
+    assert!(updated_todo.contains(
+        "\"::Bar\":\n    violations:\n    - dependency\n    - privacy\n    files:\n    - packs/foo/app/services/foo.rb\n    - packs/foo/app/services/other_foo.rb\n"
+    ));
+    assert!(!updated_todo.contains("new_foo.rb"));
+
+    common::teardown();
+    Ok(())
+}
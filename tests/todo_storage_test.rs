@@ -0,0 +1,133 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use pretty_assertions::assert_eq;
+use serial_test::serial;
+use std::{error::Error, fs, path::Path, process::Command};
+
+mod common;
+
+const CENTRAL_TODO: &str = "tests/fixtures/central_todo/packs_todo.yml";
+const FOO_TODO: &str = "tests/fixtures/central_todo/packs/foo/package_todo.yml";
+
+#[test]
+#[serial]
+fn test_check_with_central_todo() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/central_todo")
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("1 violation(s) detected:"))
+        .stdout(predicate::str::contains(
+            "packs/foo/app/services/other_foo.rb",
+        ))
+        .stdout(
+            predicate::str::contains("packs/foo/app/services/foo.rb").not(),
+        );
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_with_central_todo() -> Result<(), Box<dyn Error>> {
+    let original = fs::read_to_string(CENTRAL_TODO)?;
+
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/central_todo")
+        .arg("update")
+        .assert()
+        .try_success();
+    let updated = fs::read_to_string(CENTRAL_TODO)?;
+    fs::write(CENTRAL_TODO, &original)?;
+
+    result?;
+    assert_eq!(
+        updated,
+        original.replace(
+            "      - packs/foo/app/services/foo.rb\n",
+            "      - packs/foo/app/services/foo.rb\n      - packs/foo/app/services/other_foo.rb\n",
+        )
+    );
+    assert!(!Path::new(FOO_TODO).exists());
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_convert_todo_storage() -> Result<(), Box<dyn Error>> {
+    let original = fs::read_to_string(CENTRAL_TODO)?;
+
+    let to_per_pack = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/central_todo")
+        .arg("convert-todo-storage")
+        .arg("per-pack")
+        .assert()
+        .try_success()
+        .and_then(|assert| {
+            assert.try_stdout(predicate::str::contains(
+                "Set `todo_storage: per_pack` in packs.yml to use them",
+            ))
+        });
+    let per_pack_exists =
+        !Path::new(CENTRAL_TODO).exists() && Path::new(FOO_TODO).exists();
+    let foo_todo = fs::read_to_string(FOO_TODO).unwrap_or_default();
+
+    let to_central = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/central_todo")
+        .arg("convert-todo-storage")
+        .arg("central")
+        .assert()
+        .try_success();
+    let converted_back = fs::read_to_string(CENTRAL_TODO).unwrap_or_default();
+    let _ = fs::remove_file(FOO_TODO);
+    fs::write(CENTRAL_TODO, &original)?;
+
+    to_per_pack?;
+    to_central?;
+    assert!(per_pack_exists);
+    assert!(foo_todo.contains("# 'packs/foo' package."));
+    assert!(foo_todo.contains(
+        "packs/bar:\n  \"::Bar\":\n    violations:\n    - dependency\n    files:\n    - packs/foo/app/services/foo.rb\n"
+    ));
+    assert_eq!(converted_back, original);
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_convert_todo_storage_twice_keeps_violations(
+) -> Result<(), Box<dyn Error>> {
+    let original = fs::read_to_string(CENTRAL_TODO)?;
+
+    let convert = || -> Result<_, Box<dyn Error>> {
+        Ok(Command::cargo_bin("pks")?
+            .arg("--project-root")
+            .arg("tests/fixtures/central_todo")
+            .arg("convert-todo-storage")
+            .arg("central")
+            .assert()
+            .try_success())
+    };
+    let first = convert()?;
+    let second = convert()?;
+    let converted = fs::read_to_string(CENTRAL_TODO).unwrap_or_default();
+    fs::write(CENTRAL_TODO, &original)?;
+
+    first?;
+    second?;
+    assert_eq!(converted, original);
+    assert!(!Path::new(FOO_TODO).exists());
+
+    common::teardown();
+    Ok(())
+}
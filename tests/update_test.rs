@@ -50,6 +50,32 @@ packs/bar:
     Ok(())
 }
 
+#[test]
+#[serial]
+fn test_update_with_markdown_format() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("update")
+        .arg("--format")
+        .arg("markdown")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## packs update"))
+        .stdout(predicate::str::contains(
+            "<summary><code>packs/foo</code>: 2 recorded violation(s)</summary>",
+        ));
+
+    let package_todo_yml_filepath =
+        Path::new("tests/fixtures/simple_app/packs/foo/package_todo.yml");
+    assert!(package_todo_yml_filepath.exists());
+    std::fs::remove_file(package_todo_yml_filepath)?;
+
+    common::teardown();
+
+    Ok(())
+}
+
 #[test]
 #[serial]
 fn test_update_with_experimental_parser() -> Result<(), Box<dyn Error>> {
@@ -97,6 +123,7 @@ packs/bar:
 }
 
 #[test]
+#[serial]
 fn test_update_with_stale_violations() -> Result<(), Box<dyn Error>> {
     common::set_up_fixtures();
 
@@ -145,6 +172,242 @@ packs/bar:
     Ok(())
 }
 
+#[test]
+#[serial]
+fn test_update_prune_only_removes_stale_violations(
+) -> Result<(), Box<dyn Error>> {
+    common::set_up_fixtures();
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("update")
+        .arg("--prune-only")
+        .assert()
+        .success();
+
+    let package_todo_yml_filepath = Path::new(
+        "tests/fixtures/contains_stale_violations/packs/foo/package_todo.yml",
+    );
+    let actual = std::fs::read_to_string(package_todo_yml_filepath)?;
+    assert!(actual.contains(
+        "\
+packs/bar:
+  \"::Bar\":
+    violations:
+    - privacy
+    files:
+    - packs/foo/app/services/foo.rb
+"
+    ));
+
+    let package_todo_yml_filepath = Path::new(
+        "tests/fixtures/contains_stale_violations/packs/bar/package_todo.yml",
+    );
+    assert!(!package_todo_yml_filepath.exists());
+    common::set_up_fixtures();
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_prune_only_does_not_record_new_violations(
+) -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("update")
+        .arg("--prune-only")
+        .assert()
+        .success();
+
+    let package_todo_yml_filepath =
+        Path::new("tests/fixtures/simple_app/packs/foo/package_todo.yml");
+    assert!(!package_todo_yml_filepath.exists());
+
+    common::teardown();
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_dry_run_with_additions() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/simple_app")
+        .arg("update")
+        .arg("--dry-run")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "\
+packs/foo/package_todo.yml:
++ dependency `::Bar` (defined in `packs/bar`) in packs/foo/app/services/foo.rb
++ privacy `::Bar` (defined in `packs/bar`) in packs/foo/app/services/foo.rb
+
+2 addition(s) and 0 removal(s) to package_todo.yml files would be made",
+        ))
+        .stderr(predicate::str::contains(
+            "New violations are not recorded in package_todo.yml files, please run `packs update`",
+        ));
+
+    let package_todo_yml_filepath =
+        Path::new("tests/fixtures/simple_app/packs/foo/package_todo.yml");
+    assert!(!package_todo_yml_filepath.exists());
+
+    common::teardown();
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_dry_run_with_removals_only() -> Result<(), Box<dyn Error>> {
+    common::set_up_fixtures();
+
+    let package_todo_yml_filepath = Path::new(
+        "tests/fixtures/contains_stale_violations/packs/bar/package_todo.yml",
+    );
+    let before = std::fs::read_to_string(package_todo_yml_filepath)?;
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("update")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\
+packs/bar/package_todo.yml:
+- dependency `::Foo` (defined in `packs/foo`) in packs/bar/app/services/bar.rb
+- privacy `::Foo` (defined in `packs/foo`) in packs/bar/app/services/bar.rb
+",
+        ))
+        .stdout(predicate::str::contains(
+            "0 addition(s) and 3 removal(s) to package_todo.yml files would be made",
+        ));
+
+    let after = std::fs::read_to_string(package_todo_yml_filepath)?;
+    assert_eq!(before, after);
+
+    common::teardown();
+
+    Ok(())
+}
+
+#[test]
+fn test_update_dry_run_without_changes() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_package_todo")
+        .arg("update")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No changes to package_todo.yml files",
+        ));
+
+    common::teardown();
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_scoped_to_pack() -> Result<(), Box<dyn Error>> {
+    common::set_up_fixtures();
+
+    let bar_package_todo_yml_filepath = Path::new(
+        "tests/fixtures/contains_stale_violations/packs/bar/package_todo.yml",
+    );
+    let bar_before = std::fs::read_to_string(bar_package_todo_yml_filepath)?;
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("update")
+        .arg("--pack")
+        .arg("packs/foo")
+        .assert()
+        .success();
+
+    let foo_package_todo_yml_filepath = Path::new(
+        "tests/fixtures/contains_stale_violations/packs/foo/package_todo.yml",
+    );
+    let foo_after = std::fs::read_to_string(foo_package_todo_yml_filepath)?;
+    assert!(foo_after.contains(
+        "\
+packs/bar:
+  \"::Bar\":
+    violations:
+    - privacy
+    files:
+    - packs/foo/app/services/foo.rb
+"
+    ));
+
+    let bar_after = std::fs::read_to_string(bar_package_todo_yml_filepath)?;
+    assert_eq!(bar_before, bar_after);
+
+    common::set_up_fixtures();
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_scoped_to_files() -> Result<(), Box<dyn Error>> {
+    common::set_up_fixtures();
+
+    let foo_package_todo_yml_filepath = Path::new(
+        "tests/fixtures/contains_stale_violations/packs/foo/package_todo.yml",
+    );
+    let foo_before = std::fs::read_to_string(foo_package_todo_yml_filepath)?;
+
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("update")
+        .arg("packs/bar/app/services/bar.rb")
+        .assert()
+        .success();
+
+    let bar_package_todo_yml_filepath = Path::new(
+        "tests/fixtures/contains_stale_violations/packs/bar/package_todo.yml",
+    );
+    assert!(!bar_package_todo_yml_filepath.exists());
+
+    let foo_after = std::fs::read_to_string(foo_package_todo_yml_filepath)?;
+    assert_eq!(foo_before, foo_after);
+
+    common::set_up_fixtures();
+
+    Ok(())
+}
+
+#[test]
+fn test_update_scoped_to_unknown_pack() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/contains_stale_violations")
+        .arg("update")
+        .arg("--pack")
+        .arg("packs/does_not_exist")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No pack found 'packs/does_not_exist'",
+        ));
+
+    common::teardown();
+
+    Ok(())
+}
+
 #[test]
 fn test_update_with_packs_first_app() -> Result<(), Box<dyn Error>> {
     Command::cargo_bin("packs")?
@@ -0,0 +1,65 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{error::Error, fs, process::Command};
+
+mod common;
+
+#[test]
+fn test_check_with_ratchet_over_budget() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/violation_budget")
+        .arg("check")
+        .arg("--ratchet")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "packs/foo has 2 recorded dependency violation(s), which exceeds its dependency violation_budget of 1",
+        ))
+        .stdout(predicate::str::contains("packs/baz").not());
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_check_without_ratchet_ignores_budget() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/violation_budget")
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No violations detected!"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+fn test_update_lowers_budget() -> Result<(), Box<dyn Error>> {
+    let package_yml = "tests/fixtures/violation_budget/packs/baz/package.yml";
+    let original = fs::read_to_string(package_yml)?;
+
+    let result = Command::cargo_bin("packs")?
+        .arg("--project-root")
+        .arg("tests/fixtures/violation_budget")
+        .arg("update")
+        .arg("--pack")
+        .arg("packs/baz")
+        .assert()
+        .try_success()
+        .and_then(|assert| {
+            assert.try_stdout(predicate::str::contains(
+                "Lowered the violation_budget of packs/baz to match its package_todo.yml",
+            ))
+        });
+    let updated = fs::read_to_string(package_yml)?;
+    fs::write(package_yml, original)?;
+
+    result?;
+    assert_eq!(updated, "violation_budget: 0\n");
+
+    common::teardown();
+    Ok(())
+}
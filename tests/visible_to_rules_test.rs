@@ -0,0 +1,27 @@
+use assert_cmd::prelude::*;
+use std::{error::Error, process::Command};
+
+mod common;
+
+#[test]
+fn test_check_with_visible_to_rules() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/visible_to_rules")
+        .arg("check")
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    // packs/payments/refunds is a child, packs/checkout/cart matches the glob, and
+    // packs/ledger and finance_report.rb are owned by Finance
+    assert!(stripped_output.contains("1 violation(s) detected:"));
+    assert!(stripped_output.contains("packs/marketing/app/services/marketing.rb:3:4\nVisibility violation: `::Payments` belongs to `packs/payments`, which is not visible to `packs/marketing` (none of the visible_to rules in packs/payments/package.yml match: `children`, `owner: Finance`, `packs/checkout/**`)"));
+
+    common::teardown();
+    Ok(())
+}
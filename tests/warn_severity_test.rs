@@ -0,0 +1,99 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use serial_test::serial;
+use std::{error::Error, fs, path::Path, process::Command};
+
+mod common;
+
+const FOO_TODO: &str =
+    "tests/fixtures/warn_severity/packs/foo/package_todo.yml";
+
+#[test]
+#[serial]
+fn test_check_with_warn_setting() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/warn_severity")
+        .arg("check")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stripped_output = common::stripped_output(output);
+
+    assert!(stripped_output.contains("1 warning(s):"));
+    assert!(stripped_output.contains("packs/foo/app/services/foo.rb:3:4\nDependency violation: `::Bar` belongs to `packs/bar`"));
+    assert!(stripped_output.contains("No violations detected!"));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_check_markdown_with_warn_setting() -> Result<(), Box<dyn Error>> {
+    Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/warn_severity")
+        .arg("check")
+        .arg("--format")
+        .arg("markdown")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("### Warnings"))
+        .stdout(predicate::str::contains(
+            "| dependency | `::Bar` | `packs/bar` | [packs/foo/app/services/foo.rb:3](packs/foo/app/services/foo.rb#L3) |",
+        ));
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_check_json_with_warn_setting() -> Result<(), Box<dyn Error>> {
+    let output = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/warn_severity")
+        .arg("check")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["violations"], serde_json::json!([]));
+    let warnings = report["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["violation_type"], "dependency");
+    assert_eq!(warnings[0]["constant_name"], "::Bar");
+    assert_eq!(warnings[0]["file"], "packs/foo/app/services/foo.rb");
+    assert_eq!(warnings[0]["warning"], true);
+
+    common::teardown();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_update_does_not_record_warnings() -> Result<(), Box<dyn Error>> {
+    let result = Command::cargo_bin("pks")?
+        .arg("--project-root")
+        .arg("tests/fixtures/warn_severity")
+        .arg("update")
+        .assert()
+        .try_success();
+    let recorded = Path::new(FOO_TODO).exists();
+    let _ = fs::remove_file(FOO_TODO);
+
+    result?;
+    assert!(!recorded);
+
+    common::teardown();
+    Ok(())
+}